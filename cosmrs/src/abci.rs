@@ -0,0 +1,474 @@
+//! Application BlockChain Interface (ABCI) types used by the Cosmos SDK.
+//!
+//! <https://docs.cosmos.network/master/core/baseapp.html>
+
+pub mod events;
+
+pub use self::events::TypedEvent;
+
+use crate::{
+    proto,
+    tx::{Gas, Hash},
+    Error, ErrorReport, Result, Tx,
+};
+use eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+use subtle_encoding::hex;
+use tendermint::{block, Time};
+
+#[cfg(feature = "rpc")]
+use crate::rpc;
+
+/// Type URL of a [`Tx`] encoded as [`Any`][`crate::Any`].
+const TX_TYPE_URL: &str = "/cosmos.tx.v1beta1.Tx";
+
+/// [`TxResponse`] is the result of processing a transaction, as returned by
+/// the Cosmos SDK's `tx` service and CLI.
+#[derive(Clone, Debug)]
+pub struct TxResponse {
+    /// Height of the block containing the transaction.
+    pub height: block::Height,
+
+    /// Transaction hash.
+    pub txhash: Hash,
+
+    /// Namespace of the response [`code`][`TxResponse::code`], e.g. `sdk`.
+    pub codespace: String,
+
+    /// Response code, which is zero if the transaction succeeded.
+    pub code: u32,
+
+    /// Result bytes, if any.
+    pub data: Vec<u8>,
+
+    /// Output of the application's logger, which contains an error message if
+    /// the transaction failed, or JSON-encoded [`AbciMessageLog`]s if it
+    /// succeeded. May be non-deterministic.
+    pub raw_log: String,
+
+    /// Output of the application's logger for each message.
+    /// May be non-deterministic.
+    pub logs: Vec<AbciMessageLog>,
+
+    /// Additional information. May be non-deterministic.
+    pub info: String,
+
+    /// Amount of gas requested for the transaction.
+    pub gas_wanted: Gas,
+
+    /// Amount of gas consumed by the transaction.
+    pub gas_used: Gas,
+
+    /// Decoded transaction, if included in the response.
+    pub tx: Option<Tx>,
+
+    /// Time of the block containing the transaction, if included in the
+    /// response.
+    pub timestamp: Option<Time>,
+
+    /// All events emitted while processing the transaction, including those
+    /// emitted by the ante handler, whereas [`logs`][`TxResponse::logs`] only
+    /// contains those emitted by processing its messages.
+    pub events: Vec<Event>,
+}
+
+impl TxResponse {
+    /// Did the transaction succeed?
+    pub fn is_ok(&self) -> bool {
+        self.code == 0
+    }
+
+    /// Get the events emitted by the message at the given index.
+    pub fn msg_events(&self, msg_index: u32) -> &[Event] {
+        self.logs
+            .iter()
+            .find(|log| log.msg_index == msg_index)
+            .map(|log| log.events.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Parse all events of the given type emitted by the transaction.
+    pub fn typed_events<E: TypedEvent>(&self) -> Result<Vec<E>> {
+        self.events
+            .iter()
+            .filter(|event| E::matches(event))
+            .map(E::from_event)
+            .collect()
+    }
+
+    /// Use RPC to find the [`TxResponse`] for a transaction by its hash.
+    #[cfg(feature = "rpc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rpc")))]
+    pub async fn find_by_hash<C>(rpc_client: &C, tx_hash: Hash) -> Result<TxResponse>
+    where
+        C: rpc::Client + Send + Sync,
+    {
+        rpc_client.tx(tx_hash, false).await?.try_into()
+    }
+}
+
+impl TryFrom<proto::cosmos::base::abci::v1beta1::TxResponse> for TxResponse {
+    type Error = ErrorReport;
+
+    fn try_from(proto: proto::cosmos::base::abci::v1beta1::TxResponse) -> Result<TxResponse> {
+        let tx = match proto.tx {
+            Some(any) if any.type_url == TX_TYPE_URL => Some(Tx::from_bytes(&any.value)?),
+            Some(any) => {
+                return Err(Error::MsgType {
+                    expected: TX_TYPE_URL,
+                    found: any.type_url,
+                }
+                .into())
+            }
+            None => None,
+        };
+
+        let timestamp = match proto.timestamp.as_str() {
+            "" => None,
+            timestamp => Some(Time::parse_from_rfc3339(timestamp)?),
+        };
+
+        let logs = if proto.logs.is_empty() {
+            AbciMessageLog::parse_raw_log(proto.code, &proto.raw_log)
+        } else {
+            proto.logs.into_iter().map(Into::into).collect()
+        };
+
+        Ok(TxResponse {
+            height: proto.height.try_into()?,
+            txhash: proto.txhash.parse()?,
+            codespace: proto.codespace,
+            code: proto.code,
+            data: hex::decode(proto.data.to_ascii_lowercase())?,
+            raw_log: proto.raw_log,
+            logs,
+            info: proto.info,
+            gas_wanted: u64::try_from(proto.gas_wanted)?.into(),
+            gas_used: u64::try_from(proto.gas_used)?.into(),
+            tx,
+            timestamp,
+            events: proto
+                .events
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_>>()?,
+        })
+    }
+}
+
+/// Build a [`TxResponse`] from a `CheckTx` or `DeliverTx` result returned by
+/// Tendermint RPC.
+#[cfg(feature = "rpc")]
+macro_rules! tx_response_from_rpc {
+    ($height:expr, $hash:expr, $result:expr, $tx:expr) => {{
+        let result = $result;
+
+        TxResponse {
+            height: $height,
+            txhash: $hash,
+            codespace: result.codespace.to_string(),
+            code: result.code.value(),
+            data: result.data.value().clone(),
+            logs: AbciMessageLog::parse_raw_log(result.code.value(), result.log.as_ref()),
+            raw_log: result.log.to_string(),
+            info: result.info.to_string(),
+            gas_wanted: result.gas_wanted,
+            gas_used: result.gas_used,
+            tx: $tx,
+            timestamp: None,
+            events: result.events.into_iter().map(Into::into).collect(),
+        }
+    }};
+}
+
+#[cfg(feature = "rpc")]
+impl TryFrom<rpc::endpoint::tx::Response> for TxResponse {
+    type Error = ErrorReport;
+
+    fn try_from(response: rpc::endpoint::tx::Response) -> Result<TxResponse> {
+        let tx = Tx::from_bytes(response.tx.as_bytes())?;
+
+        Ok(tx_response_from_rpc!(
+            response.height,
+            response.hash,
+            response.tx_result,
+            Some(tx)
+        ))
+    }
+}
+
+#[cfg(feature = "rpc")]
+impl From<rpc::endpoint::broadcast::tx_commit::Response> for TxResponse {
+    fn from(response: rpc::endpoint::broadcast::tx_commit::Response) -> TxResponse {
+        // Like the Cosmos SDK, use the `CheckTx` result if the transaction
+        // failed `CheckTx` and was never executed
+        if response.check_tx.code.is_err() {
+            tx_response_from_rpc!(response.height, response.hash, response.check_tx, None)
+        } else {
+            tx_response_from_rpc!(response.height, response.hash, response.deliver_tx, None)
+        }
+    }
+}
+
+/// [`AbciMessageLog`] contains the events emitted by the message at a given
+/// index in a transaction.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AbciMessageLog {
+    /// Index of the message in the transaction.
+    #[serde(default)]
+    pub msg_index: u32,
+
+    /// Log output for the message.
+    #[serde(default)]
+    pub log: String,
+
+    /// Events emitted while executing the message.
+    #[serde(default)]
+    pub events: Vec<Event>,
+}
+
+impl AbciMessageLog {
+    /// Parse the [`AbciMessageLog`]s from the JSON-encoded raw log of a
+    /// transaction.
+    pub fn from_json(raw_log: &str) -> Result<Vec<AbciMessageLog>> {
+        serde_json::from_str(raw_log).wrap_err("raw log isn't JSON-encoded message logs")
+    }
+
+    /// Parse all events of the given type emitted by the message.
+    ///
+    /// Message logs merge events of the same type into a single event, which
+    /// are split back into the individual events before being parsed.
+    pub fn typed_events<E: TypedEvent>(&self) -> Result<Vec<E>> {
+        self.events
+            .iter()
+            .filter(|event| E::matches(event))
+            .flat_map(events::split_merged)
+            .map(|event| E::from_event(&event))
+            .collect()
+    }
+
+    /// Parse the [`AbciMessageLog`]s from the raw log of a transaction with
+    /// the given response code.
+    ///
+    /// Like the Cosmos SDK, this returns no logs if the raw log isn't
+    /// JSON-encoded message logs, which is the case if the transaction failed.
+    fn parse_raw_log(code: u32, raw_log: &str) -> Vec<AbciMessageLog> {
+        if code == 0 {
+            Self::from_json(raw_log).unwrap_or_default()
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+impl From<proto::cosmos::base::abci::v1beta1::AbciMessageLog> for AbciMessageLog {
+    fn from(proto: proto::cosmos::base::abci::v1beta1::AbciMessageLog) -> AbciMessageLog {
+        AbciMessageLog {
+            msg_index: proto.msg_index,
+            log: proto.log,
+            events: proto.events.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<AbciMessageLog> for proto::cosmos::base::abci::v1beta1::AbciMessageLog {
+    fn from(log: AbciMessageLog) -> proto::cosmos::base::abci::v1beta1::AbciMessageLog {
+        proto::cosmos::base::abci::v1beta1::AbciMessageLog {
+            msg_index: log.msg_index,
+            log: log.log,
+            events: log.events.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// [`Event`] emitted while processing a transaction, with attributes whose
+/// keys and values are strings.
+///
+/// This type is known as `StringEvent` in the Golang cosmos-sdk.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Event {
+    /// Event type, e.g. `transfer`.
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// Event attributes.
+    #[serde(default)]
+    pub attributes: Vec<Attribute>,
+}
+
+impl Event {
+    /// Get the value of the first attribute with the given key.
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.key == key)
+            .map(|attribute| attribute.value.as_str())
+    }
+}
+
+impl From<proto::cosmos::base::abci::v1beta1::StringEvent> for Event {
+    fn from(proto: proto::cosmos::base::abci::v1beta1::StringEvent) -> Event {
+        Event {
+            kind: proto.r#type,
+            attributes: proto
+                .attributes
+                .into_iter()
+                .map(|attribute| Attribute {
+                    key: attribute.key,
+                    value: attribute.value,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<Event> for proto::cosmos::base::abci::v1beta1::StringEvent {
+    fn from(event: Event) -> proto::cosmos::base::abci::v1beta1::StringEvent {
+        proto::cosmos::base::abci::v1beta1::StringEvent {
+            r#type: event.kind,
+            attributes: event
+                .attributes
+                .into_iter()
+                .map(|attribute| proto::cosmos::base::abci::v1beta1::Attribute {
+                    key: attribute.key,
+                    value: attribute.value,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<proto::tendermint::abci::Event> for Event {
+    type Error = ErrorReport;
+
+    fn try_from(proto: proto::tendermint::abci::Event) -> Result<Event> {
+        Ok(Event {
+            kind: proto.r#type,
+            attributes: proto
+                .attributes
+                .into_iter()
+                .map(|attribute| {
+                    Ok(Attribute {
+                        key: String::from_utf8(attribute.key)?,
+                        value: String::from_utf8(attribute.value)?,
+                    })
+                })
+                .collect::<Result<_>>()?,
+        })
+    }
+}
+
+impl From<tendermint::abci::Event> for Event {
+    fn from(event: tendermint::abci::Event) -> Event {
+        Event {
+            kind: event.type_str,
+            attributes: event
+                .attributes
+                .into_iter()
+                .map(|tag| Attribute {
+                    key: tag.key.to_string(),
+                    value: tag.value.to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Key/value pair attribute of an [`Event`].
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Attribute {
+    /// Attribute key.
+    pub key: String,
+
+    /// Attribute value.
+    #[serde(default)]
+    pub value: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AbciMessageLog, Event, TxResponse};
+    use crate::proto;
+
+    const RAW_LOG: &str = r#"[{"events":[{"type":"coin_received","attributes":[{"key":"receiver","value":"cosmos19dyl0uyzes4k23lscla02n06fc22h4uqsdwq6z"},{"key":"amount","value":"1000000uatom"}]},{"type":"message","attributes":[{"key":"action","value":"/cosmos.bank.v1beta1.MsgSend"}]}]},{"msg_index":1,"events":[{"type":"message","attributes":[{"key":"action","value":"/cosmos.staking.v1beta1.MsgDelegate"}]}]}]"#;
+
+    #[test]
+    fn parse_raw_log() {
+        let logs = AbciMessageLog::from_json(RAW_LOG).unwrap();
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[0].msg_index, 0);
+        assert_eq!(logs[1].msg_index, 1);
+        assert_eq!(logs[0].events[0].kind, "coin_received");
+        assert_eq!(logs[0].events[0].attribute("amount"), Some("1000000uatom"));
+
+        assert!(AbciMessageLog::from_json("out of gas").is_err());
+    }
+
+    #[test]
+    fn from_proto() {
+        let proto = proto::cosmos::base::abci::v1beta1::TxResponse {
+            height: 1234,
+            txhash: "305C2C57FD69A4557DB3FDBA95303869CE01E4505115B7C590A4842746F88820".to_owned(),
+            codespace: String::new(),
+            code: 0,
+            data: "0A1E0A1C2F636F736D6F732E62616E6B2E763162657461312E4D736753656E64".to_owned(),
+            raw_log: RAW_LOG.to_owned(),
+            logs: vec![],
+            info: String::new(),
+            gas_wanted: 200_000,
+            gas_used: 51_234,
+            tx: None,
+            timestamp: "2022-05-20T10:00:00Z".to_owned(),
+            events: vec![proto::tendermint::abci::Event {
+                r#type: "tx".to_owned(),
+                attributes: vec![proto::tendermint::abci::EventAttribute {
+                    key: b"fee".to_vec(),
+                    value: b"5000uatom".to_vec(),
+                    index: true,
+                }],
+            }],
+        };
+
+        let response = TxResponse::try_from(proto).unwrap();
+        assert!(response.is_ok());
+        assert_eq!(response.height.value(), 1234);
+        assert_eq!(response.gas_used.value(), 51_234);
+        assert_eq!(&response.data[..2], &[0x0a, 0x1e]);
+        assert_eq!(
+            response.timestamp.unwrap().to_rfc3339(),
+            "2022-05-20T10:00:00Z"
+        );
+        assert_eq!(response.events[0].attribute("fee"), Some("5000uatom"));
+
+        // Logs are parsed from the raw log
+        assert_eq!(response.logs.len(), 2);
+        assert_eq!(
+            response.msg_events(1),
+            [Event {
+                kind: "message".to_owned(),
+                attributes: vec![super::Attribute {
+                    key: "action".to_owned(),
+                    value: "/cosmos.staking.v1beta1.MsgDelegate".to_owned(),
+                }],
+            }]
+        );
+        assert!(response.msg_events(2).is_empty());
+    }
+
+    #[test]
+    fn failed_tx() {
+        let proto = proto::cosmos::base::abci::v1beta1::TxResponse {
+            txhash: "305C2C57FD69A4557DB3FDBA95303869CE01E4505115B7C590A4842746F88820".to_owned(),
+            codespace: "sdk".to_owned(),
+            code: 11,
+            raw_log: "out of gas in location: WriteFlat; gasWanted: 1, gasUsed: 1000: out of gas"
+                .to_owned(),
+            ..Default::default()
+        };
+
+        let response = TxResponse::try_from(proto).unwrap();
+        assert!(!response.is_ok());
+        assert!(response.logs.is_empty());
+        assert!(response.timestamp.is_none());
+    }
+}
@@ -0,0 +1,836 @@
+//! Typed events emitted by the Cosmos SDK's core modules, CosmWasm and IBC.
+//!
+//! Events are parsed from the attributes of an [`Event`], which may either
+//! be plain strings or Base64-encoded, as returned by the Tendermint v0.34
+//! JSON-RPC API.
+
+use super::Event;
+use crate::{proto, AccountId, Coin, Decimal, Denom, Error, ErrorReport, Result};
+use eyre::WrapErr;
+use serde::de::DeserializeOwned;
+use std::str::FromStr;
+use subtle_encoding::base64;
+use tendermint::Time;
+
+#[cfg(feature = "cosmwasm")]
+use super::Attribute;
+
+/// Events with a well-known type and attributes.
+pub trait TypedEvent: Sized {
+    /// Event type, e.g. `transfer`.
+    const KIND: &'static str;
+
+    /// Is the given [`Event`] of this type?
+    fn matches(event: &Event) -> bool {
+        event.kind == Self::KIND
+    }
+
+    /// Parse this event from the attributes of an [`Event`].
+    fn from_event(event: &Event) -> Result<Self>;
+}
+
+/// Attributes of an [`Event`] which is being parsed as a [`TypedEvent`].
+struct Attributes<'a> {
+    event: &'a Event,
+}
+
+impl<'a> Attributes<'a> {
+    /// Get the attributes of the given [`Event`], checking that it's of type `E`.
+    fn new<E: TypedEvent>(event: &'a Event) -> Result<Self> {
+        if E::matches(event) {
+            Ok(Self { event })
+        } else {
+            Err(Error::EventType {
+                expected: E::KIND,
+                found: event.kind.clone(),
+            }
+            .into())
+        }
+    }
+
+    /// Get the value of the attribute with the given key, if present.
+    ///
+    /// If the key is Base64-encoded, the value is decoded as well.
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        if let Some(value) = self.event.attribute(key) {
+            return Ok(Some(value.to_owned()));
+        }
+
+        let encoded_key = String::from_utf8(base64::encode(key))?;
+
+        match self.event.attribute(&encoded_key) {
+            Some(value) => Ok(Some(String::from_utf8(base64::decode(value)?)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Get the value of the attribute with the given key, returning an error
+    /// if it isn't present.
+    fn required(&self, key: &'static str) -> Result<String> {
+        self.get(key)?
+            .ok_or(Error::MissingField { name: key })
+            .wrap_err_with(|| format!("missing attribute in {} event", self.event.kind))
+    }
+
+    /// Parse the value of the attribute with the given key.
+    fn parse<T>(&self, key: &'static str) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: Into<ErrorReport>,
+    {
+        self.required(key)?
+            .parse()
+            .map_err(Into::into)
+            .wrap_err_with(|| format!("invalid {} attribute in {} event", key, self.event.kind))
+    }
+
+    /// Parse the value of the attribute with the given key, if present.
+    fn parse_optional<T>(&self, key: &'static str) -> Result<Option<T>>
+    where
+        T: FromStr,
+        T::Err: Into<ErrorReport>,
+    {
+        match self.get(key)?.as_deref() {
+            None | Some("") => Ok(None),
+            Some(_) => self.parse(key).map(Some),
+        }
+    }
+
+    /// Parse an amount along with its denomination, which is omitted by
+    /// Cosmos SDK versions prior to v0.46 (see [`Delegate::denom`]).
+    fn amount(&self, key: &'static str) -> Result<(Decimal, Option<Denom>)> {
+        let value = self.required(key)?;
+        let split = value
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(value.len());
+
+        let result = match value.split_at(split) {
+            (amount, "") => amount.parse().map(|amount| (amount, None)),
+            _ => value
+                .parse::<Coin>()
+                .map(|coin| (coin.amount, Some(coin.denom))),
+        };
+
+        result.wrap_err_with(|| format!("invalid {} attribute in {} event", key, self.event.kind))
+    }
+
+    /// Parse a comma-separated list of coins, e.g. `1000uatom,5stake`.
+    fn coins(&self, key: &'static str) -> Result<Vec<Coin>> {
+        self.required(key)?
+            .split(',')
+            .filter(|coin| !coin.is_empty())
+            .map(str::parse)
+            .collect::<Result<_>>()
+            .wrap_err_with(|| format!("invalid {} attribute in {} event", key, self.event.kind))
+    }
+
+    /// Parse the JSON-encoded value of the attribute with the given key, as
+    /// emitted for typed Protobuf events.
+    fn json<T: DeserializeOwned>(&self, key: &'static str) -> Result<T> {
+        serde_json::from_str(&self.required(key)?)
+            .wrap_err_with(|| format!("invalid {} attribute in {} event", key, self.event.kind))
+    }
+}
+
+/// Coins transferred from one account to another by the `bank` module.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Transfer {
+    /// Recipient's address.
+    pub recipient: AccountId,
+
+    /// Sender's address.
+    pub sender: AccountId,
+
+    /// Amount transferred.
+    pub amount: Vec<Coin>,
+}
+
+impl TypedEvent for Transfer {
+    const KIND: &'static str = "transfer";
+
+    fn from_event(event: &Event) -> Result<Self> {
+        let attributes = Attributes::new::<Self>(event)?;
+
+        Ok(Self {
+            recipient: attributes.parse("recipient")?,
+            sender: attributes.parse("sender")?,
+            amount: attributes.coins("amount")?,
+        })
+    }
+}
+
+/// Coins spent by an account.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CoinSpent {
+    /// Spender's address.
+    pub spender: AccountId,
+
+    /// Amount spent.
+    pub amount: Vec<Coin>,
+}
+
+impl TypedEvent for CoinSpent {
+    const KIND: &'static str = "coin_spent";
+
+    fn from_event(event: &Event) -> Result<Self> {
+        let attributes = Attributes::new::<Self>(event)?;
+
+        Ok(Self {
+            spender: attributes.parse("spender")?,
+            amount: attributes.coins("amount")?,
+        })
+    }
+}
+
+/// Coins received by an account.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CoinReceived {
+    /// Receiver's address.
+    pub receiver: AccountId,
+
+    /// Amount received.
+    pub amount: Vec<Coin>,
+}
+
+impl TypedEvent for CoinReceived {
+    const KIND: &'static str = "coin_received";
+
+    fn from_event(event: &Event) -> Result<Self> {
+        let attributes = Attributes::new::<Self>(event)?;
+
+        Ok(Self {
+            receiver: attributes.parse("receiver")?,
+            amount: attributes.coins("amount")?,
+        })
+    }
+}
+
+/// Coins delegated to a validator.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Delegate {
+    /// Validator's operator address.
+    pub validator: AccountId,
+
+    /// Delegator's address, which is only included by Cosmos SDK v0.46 and later.
+    pub delegator: Option<AccountId>,
+
+    /// Amount delegated.
+    pub amount: Decimal,
+
+    /// Denomination of the amount, which is only included by Cosmos SDK v0.46
+    /// and later.
+    pub denom: Option<Denom>,
+}
+
+impl TypedEvent for Delegate {
+    const KIND: &'static str = "delegate";
+
+    fn from_event(event: &Event) -> Result<Self> {
+        let attributes = Attributes::new::<Self>(event)?;
+
+        let (amount, denom) = attributes.amount("amount")?;
+
+        Ok(Self {
+            validator: attributes.parse("validator")?,
+            delegator: attributes.parse_optional("delegator")?,
+            amount,
+            denom,
+        })
+    }
+}
+
+/// Coins undelegated from a validator.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Unbond {
+    /// Validator's operator address.
+    pub validator: AccountId,
+
+    /// Amount undelegated.
+    pub amount: Decimal,
+
+    /// Denomination of the amount (see [`Delegate::denom`]).
+    pub denom: Option<Denom>,
+
+    /// Time at which the unbonding completes.
+    pub completion_time: Option<Time>,
+}
+
+impl TypedEvent for Unbond {
+    const KIND: &'static str = "unbond";
+
+    fn from_event(event: &Event) -> Result<Self> {
+        let attributes = Attributes::new::<Self>(event)?;
+
+        let (amount, denom) = attributes.amount("amount")?;
+
+        Ok(Self {
+            validator: attributes.parse("validator")?,
+            amount,
+            denom,
+            completion_time: attributes.parse_optional("completion_time")?,
+        })
+    }
+}
+
+/// Coins redelegated from one validator to another.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Redelegate {
+    /// Source validator's operator address.
+    pub source_validator: AccountId,
+
+    /// Destination validator's operator address.
+    pub destination_validator: AccountId,
+
+    /// Amount redelegated.
+    pub amount: Decimal,
+
+    /// Denomination of the amount (see [`Delegate::denom`]).
+    pub denom: Option<Denom>,
+
+    /// Time at which the redelegation completes.
+    pub completion_time: Option<Time>,
+}
+
+impl TypedEvent for Redelegate {
+    const KIND: &'static str = "redelegate";
+
+    fn from_event(event: &Event) -> Result<Self> {
+        let attributes = Attributes::new::<Self>(event)?;
+
+        let (amount, denom) = attributes.amount("amount")?;
+
+        Ok(Self {
+            source_validator: attributes.parse("source_validator")?,
+            destination_validator: attributes.parse("destination_validator")?,
+            amount,
+            denom,
+            completion_time: attributes.parse_optional("completion_time")?,
+        })
+    }
+}
+
+/// Delegation rewards withdrawn from a validator.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawRewards {
+    /// Validator's operator address.
+    pub validator: AccountId,
+
+    /// Amount withdrawn, which is empty if there were no rewards.
+    pub amount: Vec<Coin>,
+}
+
+impl TypedEvent for WithdrawRewards {
+    const KIND: &'static str = "withdraw_rewards";
+
+    fn from_event(event: &Event) -> Result<Self> {
+        let attributes = Attributes::new::<Self>(event)?;
+
+        Ok(Self {
+            validator: attributes.parse("validator")?,
+            amount: attributes.coins("amount")?,
+        })
+    }
+}
+
+/// CosmWasm contract instantiated.
+#[cfg(feature = "cosmwasm")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cosmwasm")))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Instantiate {
+    /// Address of the new contract.
+    pub contract_address: AccountId,
+
+    /// ID of the contract's code.
+    pub code_id: u64,
+}
+
+#[cfg(feature = "cosmwasm")]
+impl TypedEvent for Instantiate {
+    const KIND: &'static str = "instantiate";
+
+    fn from_event(event: &Event) -> Result<Self> {
+        let attributes = Attributes::new::<Self>(event)?;
+
+        Ok(Self {
+            contract_address: attributes.parse("_contract_address")?,
+            code_id: attributes.parse("code_id")?,
+        })
+    }
+}
+
+/// CosmWasm contract executed.
+#[cfg(feature = "cosmwasm")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cosmwasm")))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Execute {
+    /// Address of the contract.
+    pub contract_address: AccountId,
+}
+
+#[cfg(feature = "cosmwasm")]
+impl TypedEvent for Execute {
+    const KIND: &'static str = "execute";
+
+    fn from_event(event: &Event) -> Result<Self> {
+        let attributes = Attributes::new::<Self>(event)?;
+
+        Ok(Self {
+            contract_address: attributes.parse("_contract_address")?,
+        })
+    }
+}
+
+/// Event emitted by a CosmWasm contract, i.e. a `wasm` event containing the
+/// attributes a contract adds to its response, or a custom `wasm-*` event.
+#[cfg(feature = "cosmwasm")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cosmwasm")))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WasmEvent {
+    /// Event type without the `wasm-` prefix, e.g. `transfer` for a `wasm-transfer`
+    /// event, which is empty for a `wasm` event.
+    pub kind: String,
+
+    /// Address of the contract which emitted the event.
+    pub contract_address: AccountId,
+
+    /// Attributes emitted by the contract, which are never Base64-encoded.
+    pub attributes: Vec<Attribute>,
+}
+
+#[cfg(feature = "cosmwasm")]
+impl TypedEvent for WasmEvent {
+    const KIND: &'static str = "wasm";
+
+    fn matches(event: &Event) -> bool {
+        event.kind == Self::KIND || event.kind.starts_with("wasm-")
+    }
+
+    fn from_event(event: &Event) -> Result<Self> {
+        const CONTRACT_ADDRESS: &str = "_contract_address";
+
+        let attributes = Attributes::new::<Self>(event)?;
+        let encoded_key = String::from_utf8(base64::encode(CONTRACT_ADDRESS))?;
+
+        // Attributes are decoded if the contract address is Base64-encoded
+        let contract_attributes = if event.attribute(CONTRACT_ADDRESS).is_none()
+            && event.attribute(&encoded_key).is_some()
+        {
+            event
+                .attributes
+                .iter()
+                .map(|attribute| {
+                    Ok(Attribute {
+                        key: String::from_utf8(base64::decode(&attribute.key)?)?,
+                        value: String::from_utf8(base64::decode(&attribute.value)?)?,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?
+        } else {
+            event.attributes.clone()
+        };
+
+        Ok(Self {
+            kind: event
+                .kind
+                .strip_prefix("wasm-")
+                .unwrap_or_default()
+                .to_owned(),
+            contract_address: attributes.parse(CONTRACT_ADDRESS)?,
+            attributes: contract_attributes
+                .into_iter()
+                .filter(|attribute| attribute.key != CONTRACT_ADDRESS)
+                .collect(),
+        })
+    }
+}
+
+/// ICS-20 fungible token transfer sent to another chain via IBC.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IbcTransfer {
+    /// Sender's address.
+    pub sender: AccountId,
+
+    /// Receiver's address on the destination chain.
+    pub receiver: String,
+}
+
+impl TypedEvent for IbcTransfer {
+    const KIND: &'static str = "ibc_transfer";
+
+    fn from_event(event: &Event) -> Result<Self> {
+        let attributes = Attributes::new::<Self>(event)?;
+
+        Ok(Self {
+            sender: attributes.parse("sender")?,
+            receiver: attributes.required("receiver")?,
+        })
+    }
+}
+
+/// IBC packet sent.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SendPacket {
+    /// Packet data.
+    pub data: String,
+
+    /// Block height on the destination chain after which the packet times
+    /// out, e.g. `1-1000`.
+    pub timeout_height: String,
+
+    /// Time on the destination chain after which the packet times out, in
+    /// nanoseconds since the UNIX epoch, or zero if disabled.
+    pub timeout_timestamp: u64,
+
+    /// Packet sequence number.
+    pub sequence: u64,
+
+    /// Source port.
+    pub src_port: String,
+
+    /// Source channel.
+    pub src_channel: String,
+
+    /// Destination port.
+    pub dst_port: String,
+
+    /// Destination channel.
+    pub dst_channel: String,
+
+    /// Channel ordering, e.g. `ORDER_UNORDERED`.
+    pub channel_ordering: String,
+
+    /// Connection ID.
+    pub connection: String,
+}
+
+impl TypedEvent for SendPacket {
+    const KIND: &'static str = "send_packet";
+
+    fn from_event(event: &Event) -> Result<Self> {
+        let attributes = Attributes::new::<Self>(event)?;
+
+        Ok(Self {
+            data: attributes.required("packet_data")?,
+            timeout_height: attributes.required("packet_timeout_height")?,
+            timeout_timestamp: attributes.parse("packet_timeout_timestamp")?,
+            sequence: attributes.parse("packet_sequence")?,
+            src_port: attributes.required("packet_src_port")?,
+            src_channel: attributes.required("packet_src_channel")?,
+            dst_port: attributes.required("packet_dst_port")?,
+            dst_channel: attributes.required("packet_dst_channel")?,
+            channel_ordering: attributes.required("packet_channel_ordering")?,
+            connection: attributes.required("packet_connection")?,
+        })
+    }
+}
+
+/// Authorization granted by the `authz` module.
+///
+/// This is a typed Protobuf event, whose attribute values are JSON-encoded.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventGrant {
+    /// Type URL of the message the authorization applies to.
+    pub msg_type_url: String,
+
+    /// Granter's address.
+    pub granter: AccountId,
+
+    /// Grantee's address.
+    pub grantee: AccountId,
+}
+
+impl TypedEvent for EventGrant {
+    const KIND: &'static str = "cosmos.authz.v1beta1.EventGrant";
+
+    fn from_event(event: &Event) -> Result<Self> {
+        let attributes = Attributes::new::<Self>(event)?;
+
+        Ok(Self {
+            msg_type_url: attributes.json("msg_type_url")?,
+            granter: attributes.json("granter")?,
+            grantee: attributes.json("grantee")?,
+        })
+    }
+}
+
+impl TryFrom<proto::cosmos::authz::v1beta1::EventGrant> for EventGrant {
+    type Error = ErrorReport;
+
+    fn try_from(proto: proto::cosmos::authz::v1beta1::EventGrant) -> Result<EventGrant> {
+        Ok(EventGrant {
+            msg_type_url: proto.msg_type_url,
+            granter: proto.granter.parse()?,
+            grantee: proto.grantee.parse()?,
+        })
+    }
+}
+
+impl From<EventGrant> for proto::cosmos::authz::v1beta1::EventGrant {
+    fn from(event: EventGrant) -> proto::cosmos::authz::v1beta1::EventGrant {
+        proto::cosmos::authz::v1beta1::EventGrant {
+            msg_type_url: event.msg_type_url,
+            granter: event.granter.to_string(),
+            grantee: event.grantee.to_string(),
+        }
+    }
+}
+
+/// Authorization revoked by the `authz` module.
+///
+/// This is a typed Protobuf event, whose attribute values are JSON-encoded.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventRevoke {
+    /// Type URL of the message the authorization applied to.
+    pub msg_type_url: String,
+
+    /// Granter's address.
+    pub granter: AccountId,
+
+    /// Grantee's address.
+    pub grantee: AccountId,
+}
+
+impl TypedEvent for EventRevoke {
+    const KIND: &'static str = "cosmos.authz.v1beta1.EventRevoke";
+
+    fn from_event(event: &Event) -> Result<Self> {
+        let attributes = Attributes::new::<Self>(event)?;
+
+        Ok(Self {
+            msg_type_url: attributes.json("msg_type_url")?,
+            granter: attributes.json("granter")?,
+            grantee: attributes.json("grantee")?,
+        })
+    }
+}
+
+impl TryFrom<proto::cosmos::authz::v1beta1::EventRevoke> for EventRevoke {
+    type Error = ErrorReport;
+
+    fn try_from(proto: proto::cosmos::authz::v1beta1::EventRevoke) -> Result<EventRevoke> {
+        Ok(EventRevoke {
+            msg_type_url: proto.msg_type_url,
+            granter: proto.granter.parse()?,
+            grantee: proto.grantee.parse()?,
+        })
+    }
+}
+
+impl From<EventRevoke> for proto::cosmos::authz::v1beta1::EventRevoke {
+    fn from(event: EventRevoke) -> proto::cosmos::authz::v1beta1::EventRevoke {
+        proto::cosmos::authz::v1beta1::EventRevoke {
+            msg_type_url: event.msg_type_url,
+            granter: event.granter.to_string(),
+            grantee: event.grantee.to_string(),
+        }
+    }
+}
+
+/// Split an event from an [`AbciMessageLog`][`super::AbciMessageLog`] into
+/// the individual events it contains.
+///
+/// The Cosmos SDK merges all events of the same type emitted by a message
+/// into a single event in its logs, so a new event starts whenever an
+/// attribute key repeats.
+pub(super) fn split_merged(event: &Event) -> Vec<Event> {
+    let mut events = Vec::<Event>::new();
+
+    for attribute in &event.attributes {
+        match events.last_mut() {
+            Some(last) if last.attribute(&attribute.key).is_none() => {
+                last.attributes.push(attribute.clone())
+            }
+            _ => events.push(Event {
+                kind: event.kind.clone(),
+                attributes: vec![attribute.clone()],
+            }),
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CoinReceived, Delegate, EventGrant, Transfer, TypedEvent};
+    use crate::abci::{Attribute, Event};
+    use crate::Error;
+
+    fn event(kind: &str, attributes: &[(&str, &str)]) -> Event {
+        Event {
+            kind: kind.to_owned(),
+            attributes: attributes
+                .iter()
+                .map(|(key, value)| Attribute {
+                    key: (*key).to_owned(),
+                    value: (*value).to_owned(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn transfer() {
+        let plain = event(
+            "transfer",
+            &[
+                ("recipient", "cosmos19dyl0uyzes4k23lscla02n06fc22h4uqsdwq6z"),
+                ("sender", "cosmos1qyqszqgpqyqszqgpqyqszqgpqyqszqgpjnp7du"),
+                ("amount", "1000000uatom,5stake"),
+            ],
+        );
+
+        let transfer = Transfer::from_event(&plain).unwrap();
+        assert_eq!(
+            transfer.recipient.as_ref(),
+            "cosmos19dyl0uyzes4k23lscla02n06fc22h4uqsdwq6z"
+        );
+        assert_eq!(transfer.amount.len(), 2);
+        assert_eq!(transfer.amount[1].to_string(), "5stake");
+
+        // Tendermint v0.34 JSON-RPC encoding
+        let encoded = event(
+            "transfer",
+            &[
+                (
+                    "cmVjaXBpZW50",
+                    "Y29zbW9zMTlkeWwwdXl6ZXM0azIzbHNjbGEwMm4wNmZjMjJoNHVxc2R3cTZ6",
+                ),
+                (
+                    "c2VuZGVy",
+                    "Y29zbW9zMXF5cXN6cWdwcXlxc3pxZ3BxeXFzenFncHF5cXN6cWdwam5wN2R1",
+                ),
+                ("YW1vdW50", "MTAwMDAwMHVhdG9tLDVzdGFrZQ=="),
+            ],
+        );
+        assert_eq!(Transfer::from_event(&encoded).unwrap(), transfer);
+
+        let err = CoinReceived::from_event(&plain).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<Error>(),
+            Some(Error::EventType { .. })
+        ));
+    }
+
+    #[test]
+    fn delegate() {
+        // Cosmos SDK v0.45 only includes the amount without a denom
+        let delegate = Delegate::from_event(&event(
+            "delegate",
+            &[
+                (
+                    "validator",
+                    "cosmosvaloper1qypqxpqpqgpsgqgzqvzqzqsrqsqsyqcyp767eh",
+                ),
+                ("amount", "1000"),
+                ("new_shares", "1000.000000000000000000"),
+            ],
+        ))
+        .unwrap();
+
+        assert_eq!(delegate.delegator, None);
+        assert_eq!(delegate.amount, 1000u64.into());
+        assert_eq!(delegate.denom, None);
+
+        // Cosmos SDK v0.46 includes the denom
+        let delegate = Delegate::from_event(&event(
+            "delegate",
+            &[
+                (
+                    "validator",
+                    "cosmosvaloper1qypqxpqpqgpsgqgzqvzqzqsrqsqsyqcyp767eh",
+                ),
+                ("delegator", "cosmos19dyl0uyzes4k23lscla02n06fc22h4uqsdwq6z"),
+                ("amount", "1000uatom"),
+            ],
+        ))
+        .unwrap();
+
+        assert!(delegate.delegator.is_some());
+        assert_eq!(delegate.amount, 1000u64.into());
+        assert_eq!(delegate.denom, Some("uatom".parse().unwrap()));
+
+        for amount in ["", "1000ua", "uatom"] {
+            assert!(Delegate::from_event(&event(
+                "delegate",
+                &[
+                    (
+                        "validator",
+                        "cosmosvaloper1qypqxpqpqgpsgqgzqvzqzqsrqsqsyqcyp767eh",
+                    ),
+                    ("amount", amount),
+                ],
+            ))
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn event_grant() {
+        let grant = EventGrant::from_event(&event(
+            EventGrant::KIND,
+            &[
+                (
+                    "grantee",
+                    "\"cosmos19dyl0uyzes4k23lscla02n06fc22h4uqsdwq6z\"",
+                ),
+                (
+                    "granter",
+                    "\"cosmos1qyqszqgpqyqszqgpqyqszqgpqyqszqgpjnp7du\"",
+                ),
+                ("msg_type_url", "\"/cosmos.bank.v1beta1.MsgSend\""),
+            ],
+        ))
+        .unwrap();
+
+        assert_eq!(grant.msg_type_url, "/cosmos.bank.v1beta1.MsgSend");
+        assert_eq!(
+            grant.granter.as_ref(),
+            "cosmos1qyqszqgpqyqszqgpqyqszqgpqyqszqgpjnp7du"
+        );
+    }
+
+    #[cfg(feature = "cosmwasm")]
+    #[test]
+    fn wasm_event() {
+        use super::WasmEvent;
+
+        let wasm = WasmEvent::from_event(&event(
+            "wasm-transfer",
+            &[
+                (
+                    "_contract_address",
+                    "cosmos19dyl0uyzes4k23lscla02n06fc22h4uqsdwq6z",
+                ),
+                ("amount", "10"),
+            ],
+        ))
+        .unwrap();
+
+        assert_eq!(wasm.kind, "transfer");
+        assert_eq!(wasm.attributes.len(), 1);
+        assert_eq!(wasm.attributes[0].key, "amount");
+    }
+
+    #[test]
+    fn split_merged() {
+        let merged = event(
+            "coin_received",
+            &[
+                ("receiver", "cosmos19dyl0uyzes4k23lscla02n06fc22h4uqsdwq6z"),
+                ("amount", "1uatom"),
+                ("receiver", "cosmos1qyqszqgpqyqszqgpqyqszqgpqyqszqgpjnp7du"),
+                ("amount", "2uatom"),
+            ],
+        );
+
+        let events = super::split_merged(&merged)
+            .iter()
+            .map(CoinReceived::from_event)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].amount[0].to_string(), "2uatom");
+    }
+}
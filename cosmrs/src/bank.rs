@@ -2,10 +2,18 @@
 //!
 //! <https://docs.cosmos.network/master/modules/bank/>
 
-use crate::{proto, tx::Msg, AccountId, Coin, ErrorReport, Result};
+use crate::{
+    base::validate_coins,
+    proto,
+    tx::{LegacyAminoMsg, Msg},
+    AccountId, Coin, Error, ErrorReport, Result,
+};
+use eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
 
 /// MsgSend represents a message to send coins from one account to another.
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, PartialOrd, Ord, Serialize)]
 pub struct MsgSend {
     /// Sender's address.
     pub from_address: AccountId,
@@ -19,6 +27,33 @@ pub struct MsgSend {
 
 impl Msg for MsgSend {
     type Proto = proto::cosmos::bank::v1beta1::MsgSend;
+
+    fn signers(&self) -> Result<Vec<AccountId>> {
+        Ok(vec![self.from_address.clone()])
+    }
+
+    fn validate_basic(&self) -> Result<()> {
+        if self.amount.is_empty() {
+            return Err(Error::Coins {
+                coins: String::new(),
+            })
+            .wrap_err("amount is empty");
+        }
+
+        validate_coins(&self.amount).wrap_err("invalid amount")
+    }
+}
+
+impl LegacyAminoMsg for MsgSend {
+    const AMINO_TYPE: &'static str = "cosmos-sdk/MsgSend";
+
+    fn to_amino_value(&self) -> Result<serde_json::Value> {
+        Ok(json!({
+            "from_address": self.from_address,
+            "to_address": self.to_address,
+            "amount": self.amount,
+        }))
+    }
 }
 
 impl TryFrom<proto::cosmos::bank::v1beta1::MsgSend> for MsgSend {
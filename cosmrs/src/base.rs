@@ -128,7 +128,7 @@ impl Serialize for AccountId {
 }
 
 /// Coin defines a token with a denomination and an amount.
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, PartialOrd, Ord, Serialize)]
 pub struct Coin {
     /// Denomination
     pub denom: Denom,
@@ -137,6 +137,77 @@ pub struct Coin {
     pub amount: Decimal,
 }
 
+impl fmt::Display for Coin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.amount, self.denom)
+    }
+}
+
+impl FromStr for Coin {
+    type Err = ErrorReport;
+
+    /// Parse a [`Coin`] from its string representation, e.g. `1000uatom`.
+    fn from_str(s: &str) -> Result<Self> {
+        let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        let (amount, denom) = s.split_at(split);
+
+        if amount.is_empty() {
+            return Err(Error::Decimal {
+                value: s.to_owned(),
+            }
+            .into());
+        }
+
+        Ok(Coin {
+            denom: denom
+                .parse()
+                .wrap_err_with(|| format!("invalid coin: {:?}", s))?,
+            amount: amount.parse()?,
+        })
+    }
+}
+
+/// Is the given denomination valid according to the Cosmos SDK's rules, i.e.
+/// does it match `[a-zA-Z][a-zA-Z0-9/:._-]{2,127}`?
+fn is_valid_denom(denom: &str) -> bool {
+    (3..=128).contains(&denom.len())
+        && denom.starts_with(|c: char| c.is_ascii_alphabetic())
+        && denom
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | ':' | '.' | '_' | '-'))
+}
+
+/// Validate a set of coins using the same rules as the Cosmos SDK's
+/// `Coins.Validate`: every amount must be positive, and the coins must be
+/// sorted by denomination without duplicates.
+///
+/// Denominations are always valid, as they're validated by [`Denom`]'s
+/// [`FromStr`] impl.
+pub(crate) fn validate_coins(coins: &[Coin]) -> Result<()> {
+    let invalid = || Error::Coins {
+        coins: coins
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(","),
+    };
+
+    for coin in coins {
+        if coin.amount.is_zero() {
+            return Err(invalid())
+                .wrap_err_with(|| format!("coin amount must be positive: {}", coin));
+        }
+    }
+
+    for pair in coins.windows(2) {
+        if pair[0].denom >= pair[1].denom {
+            return Err(invalid()).wrap_err("coins must be sorted by denom without duplicates");
+        }
+    }
+
+    Ok(())
+}
+
 impl TryFrom<proto::cosmos::base::v1beta1::Coin> for Coin {
     type Error = ErrorReport;
 
@@ -172,6 +243,10 @@ impl From<&Coin> for proto::cosmos::base::v1beta1::Coin {
 }
 
 /// Denomination.
+///
+/// Denominations are validated using the Cosmos SDK's rules: they must be 3 to
+/// 128 characters long, start with a letter, and otherwise contain only
+/// letters, digits, and the characters `/`, `:`, `.`, `_`, and `-`.
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 pub struct Denom(String);
 
@@ -191,10 +266,7 @@ impl FromStr for Denom {
     type Err = ErrorReport;
 
     fn from_str(s: &str) -> Result<Self> {
-        // TODO(tarcieri): ensure this is the proper validation for a denom name
-        if s.chars()
-            .all(|c| matches!(c, 'A'..='Z' | 'a'..='z' | '0'..='9' | '/'))
-        {
+        if is_valid_denom(s) {
             Ok(Denom(s.to_owned()))
         } else {
             Err(Error::Denom { name: s.to_owned() }.into())
@@ -202,9 +274,23 @@ impl FromStr for Denom {
     }
 }
 
+impl<'de> Deserialize<'de> for Denom {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for Denom {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{AccountId, Denom};
+    use super::{AccountId, Coin, Denom};
 
     #[test]
     fn account_id() {
@@ -213,6 +299,24 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn coin_from_str() {
+        let coin = "1000uatom".parse::<Coin>().unwrap();
+        assert_eq!(coin.amount, 1000u64.into());
+        assert_eq!(coin.denom.as_ref(), "uatom");
+
+        let coin = "5ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+            .parse::<Coin>()
+            .unwrap();
+        assert_eq!(coin.amount, 5u64.into());
+
+        assert!("1000".parse::<Coin>().is_err());
+        assert!("uatom".parse::<Coin>().is_err());
+        assert!("1000ua".parse::<Coin>().is_err());
+        assert!("10001atom".parse::<Coin>().is_ok());
+        assert!("1000 uatom".parse::<Coin>().is_err());
+    }
+
     #[test]
     fn denom_from_str() {
         assert!(
@@ -220,5 +324,13 @@ mod tests {
                 .parse::<Denom>()
                 .is_ok()
         );
+        assert!("gamm/pool/1".parse::<Denom>().is_ok());
+        assert!("factory/osmo1abc/sub.denom_x-y:z".parse::<Denom>().is_ok());
+
+        assert!("".parse::<Denom>().is_err());
+        assert!("ua".parse::<Denom>().is_err());
+        assert!("1atom".parse::<Denom>().is_err());
+        assert!("u atom".parse::<Denom>().is_err());
+        assert!("a".repeat(129).parse::<Denom>().is_err());
     }
 }
@@ -5,19 +5,51 @@
 
 pub use crate::proto::cosmwasm::wasm::v1::AccessType;
 use crate::{
-    prost_ext::ParseOptional, proto, tx::Msg, AccountId, Coin, Error, ErrorReport, Result,
+    base::validate_coins,
+    prost_ext::ParseOptional,
+    proto, serializers,
+    tx::{LegacyAminoMsg, Msg, MsgProto},
+    AccountId, Coin, Error, ErrorReport, Result,
 };
+use eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use subtle_encoding::base64;
+
+/// Maximum size of wasm byte code accepted by `wasmd` in bytes.
+pub const MAX_WASM_SIZE: usize = 800 * 1024;
+
+/// Maximum length of a contract label accepted by `wasmd` in bytes.
+pub const MAX_LABEL_SIZE: usize = 128;
 
 /// AccessConfig access control type.
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, PartialOrd, Ord, Serialize)]
 pub struct AccessConfig {
     /// Access type granted.
+    #[serde(with = "access_type")]
     pub permission: AccessType,
 
     /// Account address with the associated permission.
     pub address: AccountId,
 }
 
+impl AccessConfig {
+    /// Serialize this [`AccessConfig`] as Amino JSON.
+    fn to_amino_value(&self) -> serde_json::Value {
+        let permission = match self.permission {
+            AccessType::Unspecified => "Unspecified",
+            AccessType::Nobody => "Nobody",
+            AccessType::OnlyAddress => "OnlyAddress",
+            AccessType::Everybody => "Everybody",
+        };
+
+        json!({
+            "permission": permission,
+            "address": self.address,
+        })
+    }
+}
+
 impl TryFrom<proto::cosmwasm::wasm::v1::AccessConfig> for AccessConfig {
     type Error = ErrorReport;
 
@@ -56,21 +88,72 @@ impl From<&AccessConfig> for proto::cosmwasm::wasm::v1::AccessConfig {
 }
 
 /// MsgStoreCode submit Wasm code to the system
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, PartialOrd, Ord, Serialize)]
 pub struct MsgStoreCode {
     /// Sender is the that actor that signed the messages
     pub sender: AccountId,
 
     /// WASMByteCode can be raw or gzip compressed
+    #[serde(with = "serializers::base64_bytes")]
     pub wasm_byte_code: Vec<u8>,
 
     /// InstantiatePermission access control to apply on contract creation,
     /// optional
+    #[serde(default)]
     pub instantiate_permission: Option<AccessConfig>,
 }
 
 impl Msg for MsgStoreCode {
     type Proto = proto::cosmwasm::wasm::v1::MsgStoreCode;
+
+    fn signers(&self) -> Result<Vec<AccountId>> {
+        Ok(vec![self.sender.clone()])
+    }
+
+    fn validate_basic(&self) -> Result<()> {
+        let invalid = || Error::InvalidMsg {
+            type_url: Self::Proto::TYPE_URL,
+        };
+
+        if self.wasm_byte_code.is_empty() {
+            return Err(invalid()).wrap_err("wasm byte code is empty");
+        }
+
+        if self.wasm_byte_code.len() > MAX_WASM_SIZE {
+            return Err(invalid()).wrap_err_with(|| {
+                format!(
+                    "wasm byte code is too large: {} bytes, max {}",
+                    self.wasm_byte_code.len(),
+                    MAX_WASM_SIZE
+                )
+            });
+        }
+
+        if let Some(config) = &self.instantiate_permission {
+            if config.permission == AccessType::Unspecified {
+                return Err(invalid()).wrap_err("instantiate permission is unspecified");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl LegacyAminoMsg for MsgStoreCode {
+    const AMINO_TYPE: &'static str = "wasm/MsgStoreCode";
+
+    fn to_amino_value(&self) -> Result<serde_json::Value> {
+        let mut value = json!({
+            "sender": self.sender,
+            "wasm_byte_code": String::from_utf8(base64::encode(&self.wasm_byte_code))?,
+        });
+
+        if let Some(permission) = &self.instantiate_permission {
+            value["instantiate_permission"] = permission.to_amino_value();
+        }
+
+        Ok(value)
+    }
 }
 
 impl TryFrom<proto::cosmwasm::wasm::v1::MsgStoreCode> for MsgStoreCode {
@@ -100,29 +183,91 @@ impl From<MsgStoreCode> for proto::cosmwasm::wasm::v1::MsgStoreCode {
 
 /// MsgInstantiateContract create a new smart contract instance for the given
 /// code id.
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, PartialOrd, Ord, Serialize)]
 pub struct MsgInstantiateContract {
     /// Sender is the that actor that signed the messages
     pub sender: AccountId,
 
     /// Admin is an optional address that can execute migrations
+    #[serde(default, with = "serializers::optional_string")]
     pub admin: Option<AccountId>,
 
     /// CodeID is the reference to the stored WASM code
+    #[serde(with = "serializers::u64_string")]
     pub code_id: u64,
 
     /// Label is optional metadata to be stored with a contract instance.
+    #[serde(default, with = "serializers::optional_string")]
     pub label: Option<String>,
 
     /// Msg json encoded message to be passed to the contract on instantiation
+    #[serde(with = "serializers::raw_json")]
     pub msg: Vec<u8>,
 
     /// Funds coins that are transferred to the contract on instantiation
+    #[serde(default)]
     pub funds: Vec<Coin>,
 }
 
 impl Msg for MsgInstantiateContract {
     type Proto = proto::cosmwasm::wasm::v1::MsgInstantiateContract;
+
+    fn signers(&self) -> Result<Vec<AccountId>> {
+        Ok(vec![self.sender.clone()])
+    }
+
+    fn validate_basic(&self) -> Result<()> {
+        let invalid = || Error::InvalidMsg {
+            type_url: Self::Proto::TYPE_URL,
+        };
+
+        if self.code_id == 0 {
+            return Err(invalid()).wrap_err("code ID is required");
+        }
+
+        match &self.label {
+            None => return Err(invalid()).wrap_err("label is required"),
+            Some(label) if label.trim().is_empty() => {
+                return Err(invalid()).wrap_err("label is required")
+            }
+            Some(label) if label.len() > MAX_LABEL_SIZE => {
+                return Err(invalid()).wrap_err_with(|| {
+                    format!(
+                        "label is too long: {} bytes, max {}",
+                        label.len(),
+                        MAX_LABEL_SIZE
+                    )
+                })
+            }
+            Some(_) => (),
+        }
+
+        validate_coins(&self.funds).wrap_err("invalid funds")?;
+        validate_json(&self.msg, invalid)
+    }
+}
+
+impl LegacyAminoMsg for MsgInstantiateContract {
+    const AMINO_TYPE: &'static str = "wasm/MsgInstantiateContract";
+
+    fn to_amino_value(&self) -> Result<serde_json::Value> {
+        let mut value = json!({
+            "sender": self.sender,
+            "code_id": self.code_id.to_string(),
+            "msg": serde_json::from_slice::<serde_json::Value>(&self.msg)?,
+            "funds": self.funds,
+        });
+
+        if let Some(admin) = &self.admin {
+            value["admin"] = json!(admin);
+        }
+
+        if let Some(label) = &self.label {
+            value["label"] = json!(label);
+        }
+
+        Ok(value)
+    }
 }
 
 impl TryFrom<proto::cosmwasm::wasm::v1::MsgInstantiateContract> for MsgInstantiateContract {
@@ -160,7 +305,7 @@ impl From<MsgInstantiateContract> for proto::cosmwasm::wasm::v1::MsgInstantiateC
 }
 
 /// MsgExecuteContract submits the given message data to a smart contract
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, PartialOrd, Ord, Serialize)]
 pub struct MsgExecuteContract {
     /// Sender is the that actor that signed the messages
     pub sender: AccountId,
@@ -169,14 +314,40 @@ pub struct MsgExecuteContract {
     pub contract: AccountId,
 
     /// Msg json encoded message to be passed to the contract
+    #[serde(with = "serializers::raw_json")]
     pub msg: Vec<u8>,
 
     /// Funds coins that are transferred to the contract on execution
+    #[serde(default)]
     pub funds: Vec<Coin>,
 }
 
 impl Msg for MsgExecuteContract {
     type Proto = proto::cosmwasm::wasm::v1::MsgExecuteContract;
+
+    fn signers(&self) -> Result<Vec<AccountId>> {
+        Ok(vec![self.sender.clone()])
+    }
+
+    fn validate_basic(&self) -> Result<()> {
+        validate_coins(&self.funds).wrap_err("invalid funds")?;
+        validate_json(&self.msg, || Error::InvalidMsg {
+            type_url: Self::Proto::TYPE_URL,
+        })
+    }
+}
+
+impl LegacyAminoMsg for MsgExecuteContract {
+    const AMINO_TYPE: &'static str = "wasm/MsgExecuteContract";
+
+    fn to_amino_value(&self) -> Result<serde_json::Value> {
+        Ok(json!({
+            "sender": self.sender,
+            "contract": self.contract,
+            "msg": serde_json::from_slice::<serde_json::Value>(&self.msg)?,
+            "funds": self.funds,
+        }))
+    }
 }
 
 impl TryFrom<proto::cosmwasm::wasm::v1::MsgExecuteContract> for MsgExecuteContract {
@@ -210,7 +381,7 @@ impl From<MsgExecuteContract> for proto::cosmwasm::wasm::v1::MsgExecuteContract
 }
 
 /// MsgMigrateContract runs a code upgrade/ downgrade for a smart contract
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, PartialOrd, Ord, Serialize)]
 pub struct MsgMigrateContract {
     /// Sender is the that actor that signed the messages
     pub sender: AccountId,
@@ -219,14 +390,45 @@ pub struct MsgMigrateContract {
     pub contract: AccountId,
 
     /// CodeID references the new WASM code
+    #[serde(with = "serializers::u64_string")]
     pub code_id: u64,
 
     /// Msg json encoded message to be passed to the contract on migration
+    #[serde(with = "serializers::raw_json")]
     pub msg: Vec<u8>,
 }
 
 impl Msg for MsgMigrateContract {
     type Proto = proto::cosmwasm::wasm::v1::MsgMigrateContract;
+
+    fn signers(&self) -> Result<Vec<AccountId>> {
+        Ok(vec![self.sender.clone()])
+    }
+
+    fn validate_basic(&self) -> Result<()> {
+        let invalid = || Error::InvalidMsg {
+            type_url: Self::Proto::TYPE_URL,
+        };
+
+        if self.code_id == 0 {
+            return Err(invalid()).wrap_err("code ID is required");
+        }
+
+        validate_json(&self.msg, invalid)
+    }
+}
+
+impl LegacyAminoMsg for MsgMigrateContract {
+    const AMINO_TYPE: &'static str = "wasm/MsgMigrateContract";
+
+    fn to_amino_value(&self) -> Result<serde_json::Value> {
+        Ok(json!({
+            "sender": self.sender,
+            "contract": self.contract,
+            "code_id": self.code_id.to_string(),
+            "msg": serde_json::from_slice::<serde_json::Value>(&self.msg)?,
+        }))
+    }
 }
 
 impl TryFrom<proto::cosmwasm::wasm::v1::MsgMigrateContract> for MsgMigrateContract {
@@ -256,7 +458,7 @@ impl From<MsgMigrateContract> for proto::cosmwasm::wasm::v1::MsgMigrateContract
 }
 
 /// MsgUpdateAdmin sets a new admin for a smart contract
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, PartialOrd, Ord, Serialize)]
 pub struct MsgUpdateAdmin {
     /// Sender is the that actor that signed the messages
     pub sender: AccountId,
@@ -270,6 +472,33 @@ pub struct MsgUpdateAdmin {
 
 impl Msg for MsgUpdateAdmin {
     type Proto = proto::cosmwasm::wasm::v1::MsgUpdateAdmin;
+
+    fn signers(&self) -> Result<Vec<AccountId>> {
+        Ok(vec![self.sender.clone()])
+    }
+
+    fn validate_basic(&self) -> Result<()> {
+        if self.sender == self.new_admin {
+            return Err(Error::InvalidMsg {
+                type_url: Self::Proto::TYPE_URL,
+            })
+            .wrap_err("new admin is the same as the old");
+        }
+
+        Ok(())
+    }
+}
+
+impl LegacyAminoMsg for MsgUpdateAdmin {
+    const AMINO_TYPE: &'static str = "wasm/MsgUpdateAdmin";
+
+    fn to_amino_value(&self) -> Result<serde_json::Value> {
+        Ok(json!({
+            "sender": self.sender,
+            "new_admin": self.new_admin,
+            "contract": self.contract,
+        }))
+    }
 }
 
 impl TryFrom<proto::cosmwasm::wasm::v1::MsgUpdateAdmin> for MsgUpdateAdmin {
@@ -309,7 +538,7 @@ impl From<&MsgUpdateAdmin> for proto::cosmwasm::wasm::v1::MsgUpdateAdmin {
 }
 
 /// MsgClearAdmin removes any admin stored for a smart contract
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, PartialOrd, Ord, Serialize)]
 pub struct MsgClearAdmin {
     /// Sender is the that actor that signed the messages
     pub sender: AccountId,
@@ -320,6 +549,21 @@ pub struct MsgClearAdmin {
 
 impl Msg for MsgClearAdmin {
     type Proto = proto::cosmwasm::wasm::v1::MsgClearAdmin;
+
+    fn signers(&self) -> Result<Vec<AccountId>> {
+        Ok(vec![self.sender.clone()])
+    }
+}
+
+impl LegacyAminoMsg for MsgClearAdmin {
+    const AMINO_TYPE: &'static str = "wasm/MsgClearAdmin";
+
+    fn to_amino_value(&self) -> Result<serde_json::Value> {
+        Ok(json!({
+            "sender": self.sender,
+            "contract": self.contract,
+        }))
+    }
 }
 
 impl TryFrom<proto::cosmwasm::wasm::v1::MsgClearAdmin> for MsgClearAdmin {
@@ -355,3 +599,106 @@ impl From<&MsgClearAdmin> for proto::cosmwasm::wasm::v1::MsgClearAdmin {
         }
     }
 }
+
+/// Check that a contract message is valid JSON, as required by `wasmd`.
+fn validate_json(msg: &[u8], invalid: impl Fn() -> Error) -> Result<()> {
+    match serde_json::from_slice::<serde_json::Value>(msg) {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            Err(invalid()).wrap_err_with(|| format!("contract message is not valid JSON: {}", e))
+        }
+    }
+}
+
+/// Serialize [`AccessType`] using the names of its Protobuf enum values.
+mod access_type {
+    use super::AccessType;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        access_type: &AccessType,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(match access_type {
+            AccessType::Unspecified => "ACCESS_TYPE_UNSPECIFIED",
+            AccessType::Nobody => "ACCESS_TYPE_NOBODY",
+            AccessType::OnlyAddress => "ACCESS_TYPE_ONLY_ADDRESS",
+            AccessType::Everybody => "ACCESS_TYPE_EVERYBODY",
+        })
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<AccessType, D::Error> {
+        match String::deserialize(deserializer)?.as_str() {
+            "ACCESS_TYPE_UNSPECIFIED" => Ok(AccessType::Unspecified),
+            "ACCESS_TYPE_NOBODY" => Ok(AccessType::Nobody),
+            "ACCESS_TYPE_ONLY_ADDRESS" => Ok(AccessType::OnlyAddress),
+            "ACCESS_TYPE_EVERYBODY" => Ok(AccessType::Everybody),
+            other => Err(de::Error::custom(format!("invalid access type: {}", other))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MsgExecuteContract, MsgInstantiateContract, MAX_LABEL_SIZE};
+    use crate::{tx::Msg, Coin, Error};
+
+    fn coin(amount: u64, denom: &str) -> Coin {
+        Coin {
+            denom: denom.parse().unwrap(),
+            amount: amount.into(),
+        }
+    }
+
+    #[test]
+    fn validate_execute_contract() {
+        let mut msg = MsgExecuteContract {
+            sender: "cosmos1qyqszqgpqyqszqgpqyqszqgpqyqszqgpjnp7du"
+                .parse()
+                .unwrap(),
+            contract: "cosmos19dyl0uyzes4k23lscla02n06fc22h4uqsdwq6z"
+                .parse()
+                .unwrap(),
+            msg: br#"{"release":{}}"#.to_vec(),
+            funds: vec![coin(1, "stake"), coin(1, "uatom")],
+        };
+        msg.validate_basic().unwrap();
+
+        msg.funds.reverse();
+        let err = msg.validate_basic().unwrap_err();
+        assert!(matches!(err.downcast_ref(), Some(Error::Coins { .. })));
+
+        msg.funds = vec![coin(1, "uatom"), coin(2, "uatom")];
+        assert!(msg.validate_basic().is_err());
+
+        msg.funds.clear();
+        msg.msg = b"not json".to_vec();
+        let err = msg.validate_basic().unwrap_err();
+        assert!(matches!(err.downcast_ref(), Some(Error::InvalidMsg { .. })));
+    }
+
+    #[test]
+    fn validate_instantiate_contract() {
+        let mut msg = MsgInstantiateContract {
+            sender: "cosmos1qyqszqgpqyqszqgpqyqszqgpqyqszqgpjnp7du"
+                .parse()
+                .unwrap(),
+            admin: None,
+            code_id: 1,
+            label: Some("example".to_owned()),
+            msg: b"{}".to_vec(),
+            funds: vec![],
+        };
+        msg.validate_basic().unwrap();
+
+        msg.label = Some("x".repeat(MAX_LABEL_SIZE + 1));
+        assert!(msg.validate_basic().is_err());
+
+        msg.label = None;
+        assert!(msg.validate_basic().is_err());
+
+        msg.label = Some("example".to_owned());
+        msg.code_id = 0;
+        assert!(msg.validate_basic().is_err());
+    }
+}
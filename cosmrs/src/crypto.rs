@@ -1,11 +1,32 @@
 //! Cryptographic functionality
 
+pub mod ed25519;
+pub mod eth_secp256k1;
 pub mod secp256k1;
+pub mod secp256r1;
 
+#[cfg(feature = "bip32")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bip32")))]
+pub mod hd;
+
+#[cfg(feature = "pkcs11")]
+#[cfg_attr(docsrs, doc(cfg(feature = "pkcs11")))]
+pub mod pkcs11;
+
+#[cfg(feature = "remote-signer")]
+#[cfg_attr(docsrs, doc(cfg(feature = "remote-signer")))]
+pub mod remote;
+
+mod async_tx_signer;
 mod compact_bit_array;
 mod legacy_amino;
 mod public_key;
+mod tx_signer;
 
 pub use self::{
-    compact_bit_array::CompactBitArray, legacy_amino::LegacyAminoMultisig, public_key::PublicKey,
+    async_tx_signer::{AsyncTxSigner, SignTxFuture},
+    compact_bit_array::CompactBitArray,
+    legacy_amino::LegacyAminoMultisig,
+    public_key::PublicKey,
+    tx_signer::TxSigner,
 };
@@ -0,0 +1,40 @@
+//! Asynchronous transaction signer trait
+
+use crate::{
+    crypto::{PublicKey, TxSigner},
+    tx::SignatureBytes,
+    Result,
+};
+use std::{future::Future, pin::Pin};
+
+/// Future returned by [`AsyncTxSigner::sign_tx_async`].
+pub type SignTxFuture<'a> = Pin<Box<dyn Future<Output = Result<SignatureBytes>> + Send + 'a>>;
+
+/// Asynchronous transaction signer trait.
+///
+/// This allows signing transactions with signers which need to perform I/O in
+/// order to produce a signature, e.g. a remote KMS or signing service such as
+/// [`RemoteSigner`][`crate::crypto::remote::RemoteSigner`], using e.g.
+/// [`SignDoc::sign_async`][`crate::tx::SignDoc::sign_async`].
+///
+/// It's impl'd for all [`TxSigner`]s, which sign synchronously. Its methods
+/// are named distinctly from those of [`TxSigner`] so calls aren't ambiguous
+/// when both traits are in scope.
+pub trait AsyncTxSigner {
+    /// Sign the given sign bytes, returning the signature as it's encoded
+    /// in a transaction.
+    fn sign_tx_async<'a>(&'a self, sign_bytes: &'a [u8]) -> SignTxFuture<'a>;
+
+    /// Get the [`PublicKey`] which verifies this signer's signatures.
+    fn signer_public_key(&self) -> PublicKey;
+}
+
+impl<T: TxSigner + ?Sized> AsyncTxSigner for T {
+    fn sign_tx_async<'a>(&'a self, sign_bytes: &'a [u8]) -> SignTxFuture<'a> {
+        Box::pin(std::future::ready(self.sign_tx(sign_bytes)))
+    }
+
+    fn signer_public_key(&self) -> PublicKey {
+        self.public_key()
+    }
+}
@@ -23,6 +23,60 @@ impl CompactBitArray {
 
         CompactBitArray { inner }
     }
+
+    /// Create a new [`CompactBitArray`] which can hold the given number of
+    /// bits, all of which are initially unset.
+    pub fn with_len(len: usize) -> CompactBitArray {
+        let extra_bits_stored = (len % 8) as u32;
+        Self::new(extra_bits_stored, vec![0u8; (len + 7) / 8])
+    }
+
+    /// Get the number of bits in this [`CompactBitArray`].
+    pub fn len(&self) -> usize {
+        let extra_bits_stored = self.inner.extra_bits_stored as usize;
+
+        match self.inner.elems.len() {
+            0 => 0,
+            n if extra_bits_stored == 0 => n * 8,
+            n => (n - 1) * 8 + extra_bits_stored,
+        }
+    }
+
+    /// Is this [`CompactBitArray`] empty?
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the value of the bit at the given index.
+    ///
+    /// Returns `false` if the index is out of range.
+    pub fn get(&self, index: usize) -> bool {
+        index < self.len() && self.inner.elems[index >> 3] & (1 << (7 - (index % 8))) != 0
+    }
+
+    /// Set the bit at the given index to the given value.
+    ///
+    /// Returns `false` if the index is out of range.
+    pub fn set(&mut self, index: usize, value: bool) -> bool {
+        if index >= self.len() {
+            return false;
+        }
+
+        let mask = 1 << (7 - (index % 8));
+
+        if value {
+            self.inner.elems[index >> 3] |= mask;
+        } else {
+            self.inner.elems[index >> 3] &= !mask;
+        }
+
+        true
+    }
+
+    /// Count the number of bits which are set.
+    pub fn count_ones(&self) -> usize {
+        (0..self.len()).filter(|&i| self.get(i)).count()
+    }
 }
 
 impl Eq for CompactBitArray {}
@@ -40,3 +94,28 @@ impl From<CompactBitArray> for proto::cosmos::crypto::multisig::v1beta1::Compact
         bitarray.inner
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::CompactBitArray;
+    use crate::proto;
+
+    #[test]
+    fn set_and_get() {
+        let mut bitarray = CompactBitArray::with_len(5);
+        assert_eq!(bitarray.len(), 5);
+        assert!(bitarray.set(0, true));
+        assert!(bitarray.set(2, true));
+        assert!(!bitarray.set(5, true));
+
+        assert!(bitarray.get(0));
+        assert!(!bitarray.get(1));
+        assert!(bitarray.get(2));
+        assert!(!bitarray.get(5));
+        assert_eq!(bitarray.count_ones(), 2);
+
+        let proto = proto::cosmos::crypto::multisig::v1beta1::CompactBitArray::from(bitarray);
+        assert_eq!(proto.extra_bits_stored, 5);
+        assert_eq!(proto.elems, [0b1010_0000]);
+    }
+}
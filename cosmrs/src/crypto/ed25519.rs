@@ -0,0 +1,6 @@
+//! Ed25519 support
+
+mod signing_key;
+
+pub use self::signing_key::{Ed25519Signer, SigningKey};
+pub use ed25519_dalek::{PublicKey as VerifyingKey, Signature};
@@ -0,0 +1,142 @@
+//! Transaction signing key
+
+use crate::{
+    crypto::{
+        ed25519::{Signature, VerifyingKey},
+        PublicKey, TxSigner,
+    },
+    tx::SignatureBytes,
+    Error, ErrorReport, Result,
+};
+use eyre::WrapErr;
+use rand_core::{OsRng, RngCore};
+
+/// Ed25519 signing key (i.e. private key)
+///
+/// This is a wrapper type which supports any pluggable Ed25519 signer
+/// implementation which impls the [`Ed25519Signer`] trait.
+///
+/// By default it uses [`ed25519_dalek::Keypair`] as the signer implementation,
+/// however it can be instantiated from any compatible signer (e.g. HSM, KMS,
+/// etc) by using [`SigningKey::new`].
+pub struct SigningKey {
+    inner: Box<dyn Ed25519Signer>,
+}
+
+impl SigningKey {
+    /// Size of an Ed25519 private key (i.e. seed) in bytes.
+    pub const BYTE_SIZE: usize = ed25519_dalek::SECRET_KEY_LENGTH;
+
+    /// Initialize from a provided signer object.
+    ///
+    /// Use [`SigningKey::from_bytes`] to initialize from a raw private key.
+    pub fn new(signer: Box<dyn Ed25519Signer>) -> Self {
+        Self { inner: signer }
+    }
+
+    /// Initialize from a raw 32-byte private key (i.e. seed).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let secret = ed25519_dalek::SecretKey::from_bytes(bytes)
+            .map_err(|_| Error::Crypto)
+            .wrap_err("invalid Ed25519 private key")?;
+
+        let public = VerifyingKey::from(&secret);
+        Ok(Self::new(Box::new(ed25519_dalek::Keypair {
+            secret,
+            public,
+        })))
+    }
+
+    /// Generate a random signing key.
+    pub fn random() -> Self {
+        let mut bytes = [0u8; Self::BYTE_SIZE];
+        OsRng.fill_bytes(&mut bytes);
+        Self::from_bytes(&bytes).expect("invalid Ed25519 private key size")
+    }
+
+    /// Sign the given message, returning a signature.
+    pub fn sign(&self, msg: &[u8]) -> Result<Signature> {
+        Ok(self.inner.try_sign(msg)?)
+    }
+
+    /// Get the [`PublicKey`] for this [`SigningKey`].
+    pub fn public_key(&self) -> PublicKey {
+        tendermint::PublicKey::from(self.inner.verifying_key()).into()
+    }
+}
+
+impl From<Box<dyn Ed25519Signer>> for SigningKey {
+    fn from(signer: Box<dyn Ed25519Signer>) -> Self {
+        Self::new(signer)
+    }
+}
+
+impl TryFrom<&[u8]> for SigningKey {
+    type Error = ErrorReport;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl TxSigner for SigningKey {
+    fn sign_tx(&self, sign_bytes: &[u8]) -> Result<SignatureBytes> {
+        Ok(self.sign(sign_bytes)?.as_ref().to_vec())
+    }
+
+    fn public_key(&self) -> PublicKey {
+        SigningKey::public_key(self)
+    }
+}
+
+/// Ed25519 signer trait.
+///
+/// This is a trait which enables plugging any backing signing implementation
+/// which produces a compatible [`Signature`] and [`VerifyingKey`].
+///
+/// Note that this trait is bounded on [`ed25519_dalek::Signer`], which is
+/// what is actually used to produce a signature for a given message.
+pub trait Ed25519Signer: ed25519_dalek::Signer<Signature> {
+    /// Get the Ed25519 [`VerifyingKey`] (i.e. public key) which corresponds
+    /// to this signer's private key.
+    fn verifying_key(&self) -> VerifyingKey;
+}
+
+impl Ed25519Signer for ed25519_dalek::Keypair {
+    fn verifying_key(&self) -> VerifyingKey {
+        self.public
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SigningKey;
+    use hex_literal::hex;
+
+    /// RFC 8032 Ed25519 test vector 1
+    const SECRET_KEY: [u8; 32] =
+        hex!("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60");
+
+    #[test]
+    fn sign_and_verify() {
+        let signing_key = SigningKey::from_bytes(&SECRET_KEY).unwrap();
+        let public_key = signing_key.public_key();
+
+        assert_eq!(
+            public_key.to_bytes(),
+            hex!("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a")
+        );
+
+        let signature = signing_key.sign(b"").unwrap();
+        assert_eq!(
+            signature.as_ref(),
+            hex!(
+                "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155"
+                "5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+            )
+        );
+
+        public_key.verify(b"", signature.as_ref()).unwrap();
+        assert!(public_key.verify(b"x", signature.as_ref()).is_err());
+    }
+}
@@ -0,0 +1,10 @@
+//! Ethermint-style ECDSA/secp256k1 support for EVM-compatible chains
+//!
+//! Keys are ordinary secp256k1 keys, but accounts use Ethereum addresses
+//! (i.e. derived using Keccak-256) and signatures are computed over the
+//! Keccak-256 digest of the sign bytes, rather than SHA-256.
+
+mod signing_key;
+
+pub use self::signing_key::{EcdsaSigner, SigningKey};
+pub use k256::ecdsa::{recoverable::Signature, VerifyingKey};
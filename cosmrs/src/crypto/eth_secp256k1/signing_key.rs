@@ -0,0 +1,224 @@
+//! Transaction signing key
+
+use crate::{
+    crypto::{eth_secp256k1::Signature, PublicKey, TxSigner},
+    tx::SignatureBytes,
+    ErrorReport, Result,
+};
+use k256::ecdsa::VerifyingKey;
+use rand_core::OsRng;
+
+#[cfg(feature = "bip32")]
+use crate::crypto::hd;
+
+/// Ethermint eth_secp256k1 signing key (i.e. private key)
+///
+/// This is a wrapper type which supports any pluggable ECDSA/secp256k1 signer
+/// implementation which impls the [`EcdsaSigner`] trait, i.e. which produces
+/// Ethereum-style recoverable signatures over the Keccak-256 digest of a
+/// message.
+///
+/// By default it uses [`k256::ecdsa::SigningKey`] as the signer implementation,
+/// however it can be instantiated from any compatible signer (e.g. HSM, KMS,
+/// etc) by using [`SigningKey::new`].
+pub struct SigningKey {
+    inner: Box<dyn EcdsaSigner>,
+}
+
+impl SigningKey {
+    /// Initialize from a provided signer object.
+    ///
+    /// Use [`SigningKey::from_bytes`] to initialize from a raw private key.
+    pub fn new(signer: Box<dyn EcdsaSigner>) -> Self {
+        Self { inner: signer }
+    }
+
+    /// Initialize from a raw scalar value (big endian).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let signing_key = k256::ecdsa::SigningKey::from_bytes(bytes)?;
+        Ok(Self::new(Box::new(signing_key)))
+    }
+
+    /// Generate a random signing key.
+    pub fn random() -> Self {
+        Self::new(Box::new(k256::ecdsa::SigningKey::random(&mut OsRng)))
+    }
+
+    /// Derive a signing key from a [`bip32::DerivationPath`], e.g.
+    /// `m/44'/60'/0'/0/0` for Ethereum's coin type.
+    ///
+    /// Note that [`bip32::DerivationPath`] impls [`std::str::FromStr`] and
+    /// therefore you can use `parse()` to parse it from a string.
+    #[cfg(feature = "bip32")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bip32")))]
+    pub fn derive_from_path(
+        seed: impl AsRef<[u8]>,
+        path: &bip32::DerivationPath,
+    ) -> bip32::Result<Self> {
+        bip32::XPrv::derive_from_path(seed, path).map(Into::into)
+    }
+
+    /// Derive a signing key from a BIP-39 mnemonic phrase and passphrase
+    /// (which is empty by default) using the given [`bip32::DerivationPath`].
+    ///
+    /// If no path is given, the default path `m/44'/60'/0'/0/0` is used.
+    /// Paths for other coin types and accounts can be computed using
+    /// [`hd::derivation_path`].
+    #[cfg(feature = "bip32")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bip32")))]
+    pub fn from_mnemonic(
+        phrase: &str,
+        passphrase: &str,
+        path: Option<&bip32::DerivationPath>,
+    ) -> Result<Self> {
+        let seed = hd::mnemonic_to_seed(phrase, passphrase)?;
+        let path = match path {
+            Some(path) => path.clone(),
+            None => hd::default_path(hd::ETHEREUM_COIN_TYPE)?,
+        };
+
+        Ok(Self::derive_from_path(seed.as_bytes(), &path)?)
+    }
+
+    /// Sign the Keccak-256 digest of the given message, returning a
+    /// recoverable signature.
+    pub fn sign(&self, msg: &[u8]) -> Result<Signature> {
+        Ok(self.inner.try_sign(msg)?)
+    }
+
+    /// Get the [`PublicKey`] for this [`SigningKey`].
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::from_eth_secp256k1(self.inner.verifying_key())
+    }
+}
+
+impl From<Box<dyn EcdsaSigner>> for SigningKey {
+    fn from(signer: Box<dyn EcdsaSigner>) -> Self {
+        Self::new(signer)
+    }
+}
+
+impl TryFrom<&[u8]> for SigningKey {
+    type Error = ErrorReport;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl TxSigner for SigningKey {
+    fn sign_tx(&self, sign_bytes: &[u8]) -> Result<SignatureBytes> {
+        Ok(self.sign(sign_bytes)?.as_ref().to_vec())
+    }
+
+    fn public_key(&self) -> PublicKey {
+        SigningKey::public_key(self)
+    }
+}
+
+#[cfg(feature = "bip32")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bip32")))]
+impl From<bip32::XPrv> for SigningKey {
+    fn from(xprv: bip32::XPrv) -> SigningKey {
+        SigningKey::from(&xprv)
+    }
+}
+
+#[cfg(feature = "bip32")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bip32")))]
+impl From<&bip32::XPrv> for SigningKey {
+    fn from(xprv: &bip32::XPrv) -> SigningKey {
+        Self {
+            inner: Box::new(xprv.private_key().clone()),
+        }
+    }
+}
+
+/// Ethermint eth_secp256k1 signer trait.
+///
+/// This is a trait which enables plugging any backing signing implementation
+/// which produces a compatible recoverable [`Signature`] and [`VerifyingKey`].
+///
+/// Note that this trait is bounded on [`ecdsa::signature::Signer`], which is
+/// what is actually used to produce a signature for a given message. For
+/// recoverable signatures, the message is hashed using Keccak-256.
+pub trait EcdsaSigner: ecdsa::signature::Signer<Signature> {
+    /// Get the ECDSA/secp256k1 [`VerifyingKey`] (i.e. public key) which
+    /// which corresponds to this signer's private key.
+    fn verifying_key(&self) -> VerifyingKey;
+}
+
+impl<T> EcdsaSigner for T
+where
+    T: ecdsa::signature::Signer<Signature>,
+    k256::ecdsa::VerifyingKey: for<'a> From<&'a T>,
+{
+    fn verifying_key(&self) -> VerifyingKey {
+        self.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SigningKey;
+    use crate::{crypto::PublicKey, tx::SignerPublicKey};
+
+    #[test]
+    fn account_id() {
+        // Private key `1`, whose Ethereum address is 0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf
+        let mut secret_key = [0u8; 32];
+        secret_key[31] = 1;
+
+        let signing_key = SigningKey::from_bytes(&secret_key).unwrap();
+        let public_key = signing_key.public_key();
+
+        assert_eq!(
+            public_key.type_url(),
+            "/ethermint.crypto.v1.ethsecp256k1.PubKey"
+        );
+        assert_eq!(
+            public_key.account_id("evmos").unwrap().as_ref(),
+            "evmos10e0525sfrf53yh2aljmm3sn9jq5njk7lxpag6e"
+        );
+        assert_eq!(
+            public_key.to_json().parse::<PublicKey>().unwrap(),
+            public_key
+        );
+    }
+
+    #[test]
+    fn any_round_trip() {
+        let public_key = SigningKey::random().public_key();
+        let any = public_key.to_any().unwrap();
+
+        assert_eq!(any.type_url, PublicKey::ETH_SECP256K1_TYPE_URL);
+        assert_eq!(PublicKey::try_from(&any).unwrap(), public_key);
+        assert_eq!(
+            SignerPublicKey::try_from(any).unwrap(),
+            SignerPublicKey::Single(public_key)
+        );
+    }
+
+    #[test]
+    fn sign_and_verify() {
+        let signing_key = SigningKey::random();
+        let public_key = signing_key.public_key();
+        let signature = signing_key.sign(b"sign bytes").unwrap();
+
+        assert_eq!(signature.as_ref().len(), 65);
+        assert_eq!(
+            PublicKey::from_eth_secp256k1(signature.recover_verify_key(b"sign bytes").unwrap()),
+            public_key
+        );
+
+        public_key
+            .verify(b"sign bytes", signature.as_ref())
+            .unwrap();
+        public_key
+            .verify(b"sign bytes", &signature.as_ref()[..64])
+            .unwrap();
+        assert!(public_key
+            .verify(b"other bytes", signature.as_ref())
+            .is_err());
+    }
+}
@@ -0,0 +1,106 @@
+//! Hierarchical deterministic (HD) key derivation using BIP-32, BIP-39
+//! mnemonics, and BIP-44 derivation paths.
+
+pub use bip32::{ChildNumber, DerivationPath, Language, Mnemonic, Seed};
+
+use crate::{Error, Result};
+use eyre::WrapErr;
+use rand_core::OsRng;
+
+/// BIP-44 coin type used by the Cosmos Hub and most other Cosmos SDK chains.
+pub const COSMOS_COIN_TYPE: u32 = 118;
+
+/// BIP-44 coin type used by Ethereum, and by EVM-compatible Cosmos SDK chains
+/// with [`eth_secp256k1`][`super::eth_secp256k1`] keys.
+pub const ETHEREUM_COIN_TYPE: u32 = 60;
+
+/// Get the BIP-44 derivation path `m/44'/{coin_type}'/{account}'/0/{index}`.
+pub fn derivation_path(coin_type: u32, account: u32, index: u32) -> Result<DerivationPath> {
+    let mut path = DerivationPath::default();
+
+    for (child, hardened) in [
+        (44, true),
+        (coin_type, true),
+        (account, true),
+        (0, false),
+        (index, false),
+    ] {
+        path.push(
+            ChildNumber::new(child, hardened)
+                .or(Err(Error::Crypto))
+                .wrap_err_with(|| format!("invalid BIP-44 child number: {}", child))?,
+        );
+    }
+
+    Ok(path)
+}
+
+/// Get the default derivation path for the given coin type, i.e. that of
+/// the first account, e.g. `m/44'/118'/0'/0/0` for [`COSMOS_COIN_TYPE`].
+pub fn default_path(coin_type: u32) -> Result<DerivationPath> {
+    derivation_path(coin_type, 0, 0)
+}
+
+/// Generate a random 24-word English [`Mnemonic`], like `gaiad keys add`.
+pub fn generate_mnemonic() -> Mnemonic {
+    Mnemonic::random(OsRng, Language::English)
+}
+
+/// Parse a 24-word English [`Mnemonic`] phrase, validating its checksum.
+///
+/// Words must be separated by single spaces.
+pub fn parse_mnemonic(phrase: &str) -> Result<Mnemonic> {
+    Mnemonic::new(phrase.trim(), Language::English)
+        .or(Err(Error::Crypto))
+        .wrap_err("invalid BIP-39 mnemonic")
+}
+
+/// Compute the BIP-39 seed for a mnemonic phrase and passphrase (which is
+/// empty by default).
+pub fn mnemonic_to_seed(phrase: &str, passphrase: &str) -> Result<Seed> {
+    Ok(parse_mnemonic(phrase)?.to_seed(passphrase))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{default_path, derivation_path, generate_mnemonic, parse_mnemonic};
+    use crate::crypto::secp256k1::SigningKey;
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon \
+        abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+        abandon abandon abandon abandon art";
+
+    #[test]
+    fn paths() {
+        assert_eq!(default_path(118).unwrap().to_string(), "m/44'/118'/0'/0/0");
+        assert_eq!(
+            derivation_path(60, 1, 2).unwrap().to_string(),
+            "m/44'/60'/1'/0/2"
+        );
+        assert!(derivation_path(1 << 31, 0, 0).is_err());
+    }
+
+    #[test]
+    fn mnemonics() {
+        let mnemonic = generate_mnemonic();
+        assert_eq!(mnemonic.phrase().split(' ').count(), 24);
+        assert!(parse_mnemonic(mnemonic.phrase()).is_ok());
+
+        // Bad checksum
+        assert!(parse_mnemonic(&MNEMONIC.replace("art", "abandon")).is_err());
+    }
+
+    #[test]
+    fn from_mnemonic() {
+        let signing_key = SigningKey::from_mnemonic(MNEMONIC, "", None).unwrap();
+
+        assert_eq!(
+            signing_key
+                .public_key()
+                .account_id("cosmos")
+                .unwrap()
+                .as_ref(),
+            "cosmos1r5v5srda7xfth3hn2s26txvrcrntldjumt8mhl"
+        );
+    }
+}
@@ -1,9 +1,28 @@
 //! Legacy Amino support.
 
 use super::PublicKey;
-use crate::{prost_ext::MessageExt, proto, Any, Error, ErrorReport, Result};
+use crate::{
+    prost_ext::MessageExt, proto, tx::SignerPublicKey, AccountId, Any, Error, ErrorReport, Result,
+};
 use eyre::WrapErr;
-use prost::Message;
+use prost::{
+    encoding::{decode_varint, encode_varint},
+    Message,
+};
+use sha2::{Digest, Sha256};
+use subtle_encoding::bech32;
+
+/// Amino prefix for `tendermint/PubKeyMultisigThreshold`.
+const MULTISIG_PREFIX: [u8; 4] = [0x22, 0xc1, 0xf7, 0xe2];
+
+/// Amino field key for the threshold (field 1, varint).
+const THRESHOLD_KEY: u8 = 0x08;
+
+/// Amino field key for the public keys (field 2, length-delimited).
+const PUBLIC_KEYS_KEY: u8 = 0x12;
+
+/// Length of a multisig address, i.e. the truncated SHA-256 digest of the key.
+const ADDRESS_LENGTH: usize = 20;
 
 /// Legacy Amino multisig key.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -12,12 +31,177 @@ pub struct LegacyAminoMultisig {
     pub threshold: u32,
 
     /// Public keys which comprise the multisig key.
-    pub public_keys: Vec<PublicKey>,
+    ///
+    /// Members are usually single keys, but may themselves be multisig keys.
+    pub public_keys: Vec<SignerPublicKey>,
 }
 
 impl LegacyAminoMultisig {
     /// Protobuf [`Any`] type URL for [`LegacyAminoMultisig`].
     pub const TYPE_URL: &'static str = "/cosmos.crypto.multisig.LegacyAminoPubKey";
+
+    /// Create a new multisig key with the given threshold and public keys,
+    /// which are kept in the given order.
+    ///
+    /// Like the Cosmos SDK, the threshold must be at least 1 and at most the
+    /// number of public keys, and the public keys must be unique.
+    ///
+    /// Public keys may be single keys (i.e. [`PublicKey`]s) or nested
+    /// multisig keys.
+    pub fn new<K>(threshold: u32, public_keys: Vec<K>) -> Result<Self>
+    where
+        K: Into<SignerPublicKey>,
+    {
+        let public_keys = public_keys.into_iter().map(Into::into).collect::<Vec<_>>();
+
+        if threshold == 0 {
+            return Err(Error::Crypto).wrap_err("multisig threshold must be at least 1");
+        }
+
+        if threshold as usize > public_keys.len() {
+            return Err(Error::Crypto).wrap_err_with(|| {
+                format!(
+                    "multisig threshold {} exceeds number of public keys: {}",
+                    threshold,
+                    public_keys.len()
+                )
+            });
+        }
+
+        for (i, public_key) in public_keys.iter().enumerate() {
+            if public_keys[..i].contains(public_key) {
+                return Err(Error::Crypto)
+                    .wrap_err_with(|| format!("duplicate multisig public key: {}", i));
+            }
+        }
+
+        Ok(Self {
+            threshold,
+            public_keys,
+        })
+    }
+
+    /// Create a new multisig key with the given threshold and public keys,
+    /// sorting the public keys by address as the Cosmos SDK does by default
+    /// (i.e. `keys add --multisig` without `--nosort`).
+    pub fn new_sorted<K>(threshold: u32, public_keys: Vec<K>) -> Result<Self>
+    where
+        K: Into<SignerPublicKey>,
+    {
+        // Addresses are compared as raw bytes, so the prefix is irrelevant
+        let mut keyed = public_keys
+            .into_iter()
+            .map(|public_key| {
+                let public_key = public_key.into();
+                Ok((
+                    member_account_id(&public_key, "cosmos")?.to_bytes(),
+                    public_key,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        keyed.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Self::new(threshold, keyed.into_iter().map(|(_, pk)| pk).collect())
+    }
+
+    /// Get the [`AccountId`] for this multisig key, i.e. the first 20 bytes of
+    /// the SHA-256 digest of its Amino encoding.
+    pub fn account_id(&self, prefix: &str) -> Result<AccountId> {
+        let digest = Sha256::digest(&self.to_amino_bytes()?);
+        AccountId::new(prefix, &digest[..ADDRESS_LENGTH])
+    }
+
+    /// Decode a multisig key from its legacy Amino binary encoding, i.e. as a
+    /// `tendermint/PubKeyMultisigThreshold`.
+    pub fn from_amino_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut bytes = bytes
+            .strip_prefix(&MULTISIG_PREFIX)
+            .ok_or(Error::Crypto)
+            .wrap_err("invalid Amino multisig prefix")?;
+
+        let mut threshold = 0;
+        let mut public_keys = Vec::new();
+
+        while let Some((&key, rest)) = bytes.split_first() {
+            bytes = rest;
+
+            match key {
+                THRESHOLD_KEY => {
+                    threshold = decode_varint(&mut bytes)?
+                        .try_into()
+                        .or(Err(Error::Crypto))
+                        .wrap_err("invalid multisig threshold")?;
+                }
+                PUBLIC_KEYS_KEY => {
+                    let len = usize::try_from(decode_varint(&mut bytes)?)?;
+
+                    if len > bytes.len() {
+                        return Err(Error::Crypto).wrap_err("truncated Amino multisig public key");
+                    }
+
+                    let (public_key, rest) = bytes.split_at(len);
+
+                    public_keys.push(if public_key.starts_with(&MULTISIG_PREFIX) {
+                        SignerPublicKey::from(Self::from_amino_bytes(public_key)?)
+                    } else {
+                        PublicKey::from_amino_bytes(public_key)?.into()
+                    });
+                    bytes = rest;
+                }
+                _ => {
+                    return Err(Error::Crypto)
+                        .wrap_err_with(|| format!("unexpected Amino multisig field: {:#x}", key))
+                }
+            }
+        }
+
+        Self::new(threshold, public_keys)
+    }
+
+    /// Encode this multisig key with the legacy Amino binary encoding, i.e. as
+    /// a `tendermint/PubKeyMultisigThreshold`.
+    pub fn to_amino_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = MULTISIG_PREFIX.to_vec();
+
+        // Amino omits default values
+        if self.threshold != 0 {
+            bytes.push(THRESHOLD_KEY);
+            encode_varint(self.threshold.into(), &mut bytes);
+        }
+
+        for public_key in &self.public_keys {
+            let encoded = match public_key {
+                SignerPublicKey::Single(public_key) => public_key.to_amino_bytes()?,
+                SignerPublicKey::LegacyAminoMultisig(multisig) => multisig.to_amino_bytes()?,
+                SignerPublicKey::Any(any) => {
+                    return Err(Error::Crypto).wrap_err_with(|| {
+                        format!("no Amino encoding for public key type: {}", any.type_url)
+                    })
+                }
+            };
+
+            bytes.push(PUBLIC_KEYS_KEY);
+            encode_varint(encoded.len() as u64, &mut bytes);
+            bytes.extend_from_slice(&encoded);
+        }
+
+        Ok(bytes)
+    }
+
+    /// Parse a multisig key from the legacy Bech32 format, i.e. the Bech32
+    /// encoding of its Amino binary encoding, e.g. `cosmospub1...`.
+    ///
+    /// The human-readable prefix is not checked.
+    pub fn from_bech32(s: &str) -> Result<Self> {
+        let (_, bytes) = bech32::decode(s).wrap_err_with(|| format!("invalid bech32: '{}'", s))?;
+        Self::from_amino_bytes(&bytes)
+    }
+
+    /// Serialize this multisig key in the legacy Bech32 format with the given
+    /// human-readable prefix, e.g. `cosmospub`.
+    pub fn to_bech32(&self, prefix: &str) -> Result<String> {
+        Ok(bech32::encode(prefix, self.to_amino_bytes()?))
+    }
 }
 
 impl From<LegacyAminoMultisig> for Any {
@@ -62,7 +246,7 @@ impl TryFrom<&Any> for LegacyAminoMultisig {
         let public_keys = proto
             .public_keys
             .into_iter()
-            .map(PublicKey::try_from)
+            .map(SignerPublicKey::try_from)
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(Self {
@@ -72,12 +256,145 @@ impl TryFrom<&Any> for LegacyAminoMultisig {
     }
 }
 
+/// Get the [`AccountId`] of a member of a multisig key.
+fn member_account_id(public_key: &SignerPublicKey, prefix: &str) -> Result<AccountId> {
+    match public_key {
+        SignerPublicKey::Single(public_key) => public_key.account_id(prefix),
+        SignerPublicKey::LegacyAminoMultisig(multisig) => multisig.account_id(prefix),
+        SignerPublicKey::Any(any) => Err(Error::Crypto)
+            .wrap_err_with(|| format!("unsupported public key type: {}", any.type_url)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::LegacyAminoMultisig;
-    use crate::{crypto::PublicKey, Any};
+    use crate::{crypto::PublicKey, tx::SignerPublicKey, Any};
     use hex_literal::hex;
 
+    /// Public keys of the multisig key in `any_round_trip`, sorted by address.
+    const PUBLIC_KEYS: [[u8; 33]; 5] = [
+        hex!("0316eb99be27392e258ded83dc1378e507acf1bb726fa407167e709461b3a631cb"),
+        hex!("0363deebf13d30a9840f275d01911f3e05f3fb5f88554f52b2ef534dce06b1da59"),
+        hex!("032e253cf8214f3d466ed296b9919821ae6681806c91b3c2063a45a8b85ce7e115"),
+        hex!("0326ffd12bd115f260a371f2f09bf29286e4c9681c7bc109f4604c82ed82d6d232"),
+        hex!("0343a3b485021493370286c9f4725358a3fd459576f963dcc158cb82c02276b67f"),
+    ];
+
+    fn public_keys() -> Vec<SignerPublicKey> {
+        PUBLIC_KEYS
+            .iter()
+            .map(|bytes| {
+                PublicKey::from_raw(PublicKey::SECP256K1_TYPE_URL, bytes)
+                    .unwrap()
+                    .into()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn new_validation() {
+        assert!(LegacyAminoMultisig::new(0, public_keys()).is_err());
+        assert!(LegacyAminoMultisig::new(6, public_keys()).is_err());
+        assert!(LegacyAminoMultisig::new(1, Vec::<PublicKey>::new()).is_err());
+
+        let mut duplicated = public_keys();
+        duplicated.push(duplicated[2].clone());
+        assert!(LegacyAminoMultisig::new(3, duplicated).is_err());
+
+        let multisig = LegacyAminoMultisig::new(5, public_keys()).unwrap();
+        assert_eq!(multisig.threshold, 5);
+        assert_eq!(multisig.public_keys, public_keys());
+    }
+
+    #[test]
+    fn new_sorted() {
+        let mut reversed = public_keys();
+        reversed.reverse();
+
+        let unsorted = LegacyAminoMultisig::new(3, reversed.clone()).unwrap();
+        assert_eq!(unsorted.public_keys, reversed);
+
+        let sorted = LegacyAminoMultisig::new_sorted(3, reversed).unwrap();
+        assert_eq!(sorted.public_keys, public_keys());
+    }
+
+    #[test]
+    fn account_id() {
+        let multisig = LegacyAminoMultisig::new(3, public_keys()).unwrap();
+        assert_eq!(
+            multisig.account_id("cosmos").unwrap().as_ref(),
+            "cosmos16plylpsgxechajltx9yeseqexzdzut9g8vla4k"
+        );
+
+        // Key order affects the address
+        let mut reversed = public_keys();
+        reversed.reverse();
+        let multisig = LegacyAminoMultisig::new(3, reversed).unwrap();
+        assert_ne!(
+            multisig.account_id("cosmos").unwrap().as_ref(),
+            "cosmos16plylpsgxechajltx9yeseqexzdzut9g8vla4k"
+        );
+    }
+
+    #[test]
+    fn amino_round_trip() {
+        let multisig = LegacyAminoMultisig::new(3, public_keys()).unwrap();
+        let amino_bytes = multisig.to_amino_bytes().unwrap();
+        assert_eq!(&amino_bytes[..8], hex!("22c1f7e208031226"));
+        assert_eq!(amino_bytes.len(), 6 + 5 * 40);
+        assert_eq!(
+            LegacyAminoMultisig::from_amino_bytes(&amino_bytes).unwrap(),
+            multisig
+        );
+
+        let bech32 = multisig.to_bech32("cosmospub").unwrap();
+        assert!(bech32.starts_with("cosmospub1ytql0csgqvfzd666"));
+        assert_eq!(LegacyAminoMultisig::from_bech32(&bech32).unwrap(), multisig);
+
+        assert!(LegacyAminoMultisig::from_amino_bytes(&amino_bytes[..100]).is_err());
+        assert!(LegacyAminoMultisig::from_amino_bytes(&amino_bytes[4..]).is_err());
+
+        // Decoded keys are subject to the same validation as `new`
+        let mut invalid_threshold = amino_bytes.clone();
+        invalid_threshold[5] = 6;
+        assert!(LegacyAminoMultisig::from_amino_bytes(&invalid_threshold).is_err());
+
+        let zero_threshold = [&amino_bytes[..4], &amino_bytes[6..]].concat();
+        assert!(LegacyAminoMultisig::from_amino_bytes(&zero_threshold).is_err());
+    }
+
+    #[test]
+    fn nested_amino_round_trip() {
+        let keys = public_keys();
+        let inner = LegacyAminoMultisig::new(2, keys[..3].to_vec()).unwrap();
+        let outer = LegacyAminoMultisig::new(
+            2,
+            vec![inner.clone().into(), keys[3].clone(), keys[4].clone()],
+        )
+        .unwrap();
+
+        let amino_bytes = outer.to_amino_bytes().unwrap();
+        let decoded = LegacyAminoMultisig::from_amino_bytes(&amino_bytes).unwrap();
+        assert_eq!(decoded, outer);
+        assert_eq!(decoded.public_keys[0].legacy_amino_multisig(), Some(&inner));
+
+        assert_eq!(
+            LegacyAminoMultisig::try_from(Any::from(outer.clone())).unwrap(),
+            outer
+        );
+        assert!(outer.account_id("cosmos").is_ok());
+        assert!(LegacyAminoMultisig::new_sorted(2, outer.public_keys).is_ok());
+    }
+
+    #[test]
+    fn account_id_unsupported_key() {
+        let signing_key = p256::ecdsa::SigningKey::random(&mut rand_core::OsRng);
+        let public_key = PublicKey::from(signing_key.verifying_key());
+        let multisig = LegacyAminoMultisig::new(1, vec![public_key]).unwrap();
+        assert!(multisig.account_id("cosmos").is_err());
+    }
+
     #[test]
     fn any_round_trip() {
         let any = Any {
@@ -91,10 +408,12 @@ mod tests {
         assert_eq!(pk.public_keys[0].type_url(), PublicKey::SECP256K1_TYPE_URL);
         assert_eq!(
             pk.public_keys[0],
-            tendermint::PublicKey::from_raw_secp256k1(&hex!(
-                "0316eb99be27392e258ded83dc1378e507acf1bb726fa407167e709461b3a631cb"
-            ))
-            .unwrap()
+            PublicKey::from(
+                tendermint::PublicKey::from_raw_secp256k1(&hex!(
+                    "0316eb99be27392e258ded83dc1378e507acf1bb726fa407167e709461b3a631cb"
+                ))
+                .unwrap()
+            )
             .into()
         );
 
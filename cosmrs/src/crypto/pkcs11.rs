@@ -0,0 +1,337 @@
+//! PKCS#11 signer for ECDSA/secp256k1 keys held by an HSM or other token.
+//!
+//! [`Signer`] impls [`EcdsaSigner`], so it can be used as the backing
+//! implementation of a [`SigningKey`]:
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+//! use cosmrs::crypto::{pkcs11, secp256k1};
+//!
+//! let module = pkcs11::Module::load("/usr/lib/softhsm/libsofthsm2.so")?;
+//! let signer = pkcs11::Signer::open(&module, "cosmrs", "1234", "validator")?;
+//! let signing_key = secp256k1::SigningKey::new(Box::new(signer));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Keys are looked up by the label of their private key object, and must be
+//! EC keys over secp256k1 with a corresponding public key object which has
+//! either the same `CKA_ID` or, if the private key has no ID, the same label.
+//!
+//! Tokens sign the SHA-256 digest of the message using `CKM_ECDSA`, and
+//! signatures are normalized to have a "low" `s` value, as required by the
+//! Cosmos SDK. Both raw (i.e. `r || s`) and ASN.1 DER signatures are accepted.
+//!
+//! The PKCS#11 bindings themselves are provided by the [`cryptoki`] crate.
+//!
+//! [`EcdsaSigner`]: crate::crypto::secp256k1::EcdsaSigner
+//! [`SigningKey`]: crate::crypto::secp256k1::SigningKey
+
+use crate::{Error, Result};
+use cryptoki::{
+    context::{CInitializeArgs, Pkcs11},
+    mechanism::Mechanism,
+    object::{Attribute, AttributeType, KeyType, ObjectClass, ObjectHandle},
+    session::{Session, UserType},
+    types::AuthPin,
+};
+use ecdsa::signature::{self, digest::Digest};
+use eyre::WrapErr;
+use k256::ecdsa::{Signature, VerifyingKey};
+use sha2::Sha256;
+use std::{path::Path, sync::Mutex};
+
+/// DER-encoded OID of the secp256k1 curve, i.e. the expected `CKA_EC_PARAMS`.
+const SECP256K1_OID: &[u8] = &[0x06, 0x05, 0x2b, 0x81, 0x04, 0x00, 0x0a];
+
+/// ASN.1 DER tag of an OCTET STRING.
+const OCTET_STRING_TAG: u8 = 0x04;
+
+/// PKCS#11 module, i.e. a dynamically loaded library which provides access
+/// to a token such as an HSM.
+///
+/// The library is finalized when the [`Module`] and all [`Signer`]s opened
+/// with it have been dropped.
+#[derive(Clone)]
+pub struct Module {
+    pkcs11: Pkcs11,
+}
+
+impl Module {
+    /// Load and initialize the PKCS#11 module at the given path, e.g.
+    /// `/usr/lib/softhsm/libsofthsm2.so`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let pkcs11 = Pkcs11::new(path)
+            .wrap_err_with(|| format!("error loading PKCS#11 library: {:?}", path))?;
+
+        pkcs11
+            .initialize(CInitializeArgs::OsThreads)
+            .wrap_err_with(|| format!("error initializing PKCS#11 library: {:?}", path))?;
+
+        Ok(Self { pkcs11 })
+    }
+
+    /// Get the labels of all tokens which are present.
+    pub fn token_labels(&self) -> Result<Vec<String>> {
+        self.pkcs11
+            .get_slots_with_token()?
+            .into_iter()
+            .map(|slot| Ok(self.pkcs11.get_token_info(slot)?.label().to_owned()))
+            .collect()
+    }
+}
+
+/// ECDSA/secp256k1 signer backed by a private key held by a PKCS#11 token.
+pub struct Signer {
+    /// Session with the token, which can only perform one operation at a time.
+    session: Mutex<Session>,
+
+    /// Private key object.
+    private_key: ObjectHandle,
+
+    /// Public key corresponding to the private key.
+    verifying_key: VerifyingKey,
+}
+
+impl Signer {
+    /// Open a session with the token with the given label, log in with the
+    /// given user PIN, and find the private key with the given label.
+    pub fn open(module: &Module, token_label: &str, pin: &str, key_label: &str) -> Result<Self> {
+        let pkcs11 = &module.pkcs11;
+
+        let slot = pkcs11
+            .get_slots_with_token()?
+            .into_iter()
+            .find(|&slot| {
+                matches!(pkcs11.get_token_info(slot), Ok(info) if info.label() == token_label)
+            })
+            .ok_or(Error::Crypto)
+            .wrap_err_with(|| format!("PKCS#11 token not found: {:?}", token_label))?;
+
+        let session = pkcs11.open_ro_session(slot)?;
+        session
+            .login(UserType::User, Some(&AuthPin::new(pin.to_owned())))
+            .wrap_err_with(|| format!("error logging in to PKCS#11 token: {:?}", token_label))?;
+
+        let label = Attribute::Label(key_label.as_bytes().to_vec());
+        let private_key = find_key(&session, ObjectClass::PRIVATE_KEY, label.clone())?;
+
+        let public_key = match attribute(&session, private_key, AttributeType::Id)? {
+            Some(Attribute::Id(id)) if !id.is_empty() => {
+                find_key(&session, ObjectClass::PUBLIC_KEY, Attribute::Id(id))?
+            }
+            _ => find_key(&session, ObjectClass::PUBLIC_KEY, label)?,
+        };
+
+        match attribute(&session, public_key, AttributeType::EcParams)? {
+            Some(Attribute::EcParams(params)) if params == SECP256K1_OID => (),
+            _ => {
+                return Err(Error::Crypto).wrap_err_with(|| {
+                    format!("PKCS#11 key isn't a secp256k1 key: {:?}", key_label)
+                })
+            }
+        }
+
+        let verifying_key = match attribute(&session, public_key, AttributeType::EcPoint)? {
+            Some(Attribute::EcPoint(point)) => parse_ec_point(&point)?,
+            _ => {
+                return Err(Error::Crypto).wrap_err_with(|| {
+                    format!("PKCS#11 public key has no EC point: {:?}", key_label)
+                })
+            }
+        };
+
+        Ok(Self {
+            session: Mutex::new(session),
+            private_key,
+            verifying_key,
+        })
+    }
+
+    /// Get the [`VerifyingKey`] (i.e. public key) of the signer's private key.
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.verifying_key
+    }
+}
+
+impl signature::Signer<Signature> for Signer {
+    fn try_sign(&self, msg: &[u8]) -> signature::Result<Signature> {
+        let digest = Sha256::digest(msg);
+        let session = self.session.lock().map_err(|_| signature::Error::new())?;
+
+        let signature = session
+            .sign(&Mechanism::Ecdsa, self.private_key, &digest)
+            .map_err(|_| signature::Error::new())?;
+
+        normalize_signature(&signature).map_err(|_| signature::Error::new())
+    }
+}
+
+impl From<&Signer> for VerifyingKey {
+    fn from(signer: &Signer) -> VerifyingKey {
+        signer.verifying_key
+    }
+}
+
+/// Find the single EC key object of the given class with the given attribute.
+fn find_key(session: &Session, class: ObjectClass, attribute: Attribute) -> Result<ObjectHandle> {
+    let value = match &attribute {
+        Attribute::Label(value) | Attribute::Id(value) => {
+            String::from_utf8_lossy(value).into_owned()
+        }
+        other => format!("{:?}", other),
+    };
+
+    let objects = session.find_objects(&[
+        Attribute::Class(class),
+        Attribute::KeyType(KeyType::EC),
+        attribute,
+    ])?;
+
+    match objects.as_slice() {
+        [object] => Ok(*object),
+        [] => Err(Error::KeyNotFound { name: value }.into()),
+        _ => Err(Error::Crypto)
+            .wrap_err_with(|| format!("found {} PKCS#11 keys matching {:?}", objects.len(), value)),
+    }
+}
+
+/// Get the value of the given attribute of an object, if it has one.
+fn attribute(
+    session: &Session,
+    object: ObjectHandle,
+    attribute: AttributeType,
+) -> Result<Option<Attribute>> {
+    Ok(session
+        .get_attributes(object, &[attribute])?
+        .into_iter()
+        .next())
+}
+
+/// Parse a signature returned by a token, which is either a raw `r || s`
+/// signature or ASN.1 DER, normalizing it to have a "low" `s` value.
+fn normalize_signature(bytes: &[u8]) -> Result<Signature> {
+    let signature = if bytes.len() == 64 {
+        Signature::try_from(bytes)?
+    } else {
+        Signature::from_der(bytes)?
+    };
+
+    Ok(signature.normalize_s().unwrap_or(signature))
+}
+
+/// Parse a `CKA_EC_POINT` value, which is a DER-encoded OCTET STRING
+/// containing a SEC1-encoded point, although some tokens omit the OCTET STRING.
+///
+/// A raw uncompressed point can look like an OCTET STRING (e.g. when its X
+/// coordinate starts with `0x3f`), so if the contents of what looks like an
+/// OCTET STRING aren't a valid point, the value is parsed as a raw point.
+fn parse_ec_point(bytes: &[u8]) -> Result<VerifyingKey> {
+    if let [OCTET_STRING_TAG, len, point @ ..] = bytes {
+        if usize::from(*len) == point.len() {
+            if let Ok(verifying_key) = VerifyingKey::from_sec1_bytes(point) {
+                return Ok(verifying_key);
+            }
+        }
+    }
+
+    VerifyingKey::from_sec1_bytes(bytes).wrap_err("invalid PKCS#11 EC point")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize_signature, parse_ec_point, Module, Signer};
+    use ecdsa::signature::{Signer as _, Verifier};
+    use k256::{
+        ecdsa::{Signature, SigningKey},
+        elliptic_curve::sec1::ToEncodedPoint,
+    };
+    use rand_core::OsRng;
+
+    #[test]
+    fn normalize_high_s_signature() {
+        let signing_key = SigningKey::random(&mut OsRng);
+        let signature: Signature = signing_key.sign(b"example");
+        let high_s = Signature::from_scalars(*signature.r(), -*signature.s()).unwrap();
+        assert!(high_s.normalize_s().is_some());
+
+        for bytes in [
+            high_s.as_ref().to_vec(),
+            high_s.to_der().as_bytes().to_vec(),
+        ] {
+            let normalized = normalize_signature(&bytes).unwrap();
+            assert_eq!(normalized, signature);
+            signing_key
+                .verifying_key()
+                .verify(b"example", &normalized)
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn parse_wrapped_and_unwrapped_ec_point() {
+        let verifying_key = SigningKey::random(&mut OsRng).verifying_key();
+        let point = verifying_key.to_encoded_point(false);
+
+        let mut wrapped = vec![0x04, point.len() as u8];
+        wrapped.extend_from_slice(point.as_bytes());
+
+        assert_eq!(parse_ec_point(&wrapped).unwrap(), verifying_key);
+        assert_eq!(parse_ec_point(point.as_bytes()).unwrap(), verifying_key);
+        assert!(parse_ec_point(&[0x04, 0x02, 0x03]).is_err());
+    }
+
+    #[test]
+    fn parse_unwrapped_ec_point_resembling_octet_string() {
+        // Find a key whose uncompressed point looks like a DER OCTET STRING,
+        // i.e. `0x04` followed by an X coordinate starting with `0x3f`
+        let verifying_key = (1u64..)
+            .map(|i| {
+                let mut secret_key = [0u8; 32];
+                secret_key[24..].copy_from_slice(&i.to_be_bytes());
+                SigningKey::from_bytes(&secret_key).unwrap().verifying_key()
+            })
+            .find(|key| key.to_encoded_point(false).as_bytes()[1] == 0x3f)
+            .unwrap();
+
+        let point = verifying_key.to_encoded_point(false);
+        assert_eq!(parse_ec_point(point.as_bytes()).unwrap(), verifying_key);
+    }
+
+    /// Sign using a secp256k1 key held by SoftHSM (or another PKCS#11 module).
+    ///
+    /// Set up a token and key with:
+    ///
+    /// ```text
+    /// softhsm2-util --init-token --free --label cosmrs --pin 1234 --so-pin 1234
+    /// pkcs11-tool --module $PKCS11_MODULE --token-label cosmrs --login --pin 1234 \
+    ///     --keypairgen --key-type EC:secp256k1 --label validator
+    /// ```
+    ///
+    /// and run with `PKCS11_MODULE=... cargo test --features pkcs11 -- --ignored`.
+    #[test]
+    #[ignore]
+    fn sign_with_softhsm() {
+        let path = std::env::var("PKCS11_MODULE")
+            .unwrap_or_else(|_| "/usr/lib/softhsm/libsofthsm2.so".to_owned());
+        let pin = std::env::var("PKCS11_PIN").unwrap_or_else(|_| "1234".to_owned());
+
+        let module = Module::load(path).unwrap();
+        assert!(module.token_labels().unwrap().iter().any(|l| l == "cosmrs"));
+
+        let signer = Signer::open(&module, "cosmrs", &pin, "validator").unwrap();
+        let signature: Signature = signer.sign(b"example");
+        assert!(signature.normalize_s().is_none());
+
+        signer
+            .verifying_key()
+            .verify(b"example", &signature)
+            .unwrap();
+
+        let signing_key = crate::crypto::secp256k1::SigningKey::new(Box::new(signer));
+        let public_key = signing_key.public_key();
+        let signature = signing_key.sign(b"example").unwrap();
+        assert!(public_key.verify(b"example", signature.as_ref()).is_ok());
+    }
+}
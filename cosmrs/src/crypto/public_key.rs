@@ -1,17 +1,43 @@
 //! Public keys
 
 use crate::{prost_ext::MessageExt, proto, AccountId, Error, ErrorReport, Result};
+use ecdsa::signature::{DigestVerifier, Verifier};
 use eyre::WrapErr;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
 use prost::Message;
 use prost_types::Any;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
 use std::str::FromStr;
-use subtle_encoding::base64;
+use subtle_encoding::{base64, bech32};
+
+/// Amino prefix for Ed25519 public keys, i.e. `tendermint/PubKeyEd25519`.
+const AMINO_ED25519_PREFIX: [u8; 4] = [0x16, 0x24, 0xde, 0x64];
+
+/// Amino prefix for secp256k1 public keys, i.e. `tendermint/PubKeySecp256k1`.
+const AMINO_SECP256K1_PREFIX: [u8; 4] = [0xeb, 0x5a, 0xe9, 0x87];
 
 /// Public keys
 #[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(try_from = "PublicKeyJson", into = "PublicKeyJson")]
-pub struct PublicKey(tendermint::PublicKey);
+pub struct PublicKey(Inner);
+
+/// Inner enum for the supported public key types.
+///
+/// Keys supported by Tendermint (i.e. Ed25519 and secp256k1) are stored as a
+/// [`tendermint::PublicKey`], while the remaining keys are stored separately.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Inner {
+    /// Ed25519 and secp256k1 public keys.
+    Tendermint(tendermint::PublicKey),
+
+    /// ECDSA/secp256r1 (P-256) public keys.
+    Secp256r1(p256::ecdsa::VerifyingKey),
+
+    /// Ethermint-style ECDSA/secp256k1 public keys with Ethereum addresses.
+    EthSecp256k1(k256::ecdsa::VerifyingKey),
+}
 
 impl PublicKey {
     /// Protobuf [`Any`] type URL for Ed25519 public keys
@@ -20,6 +46,21 @@ impl PublicKey {
     /// Protobuf [`Any`] type URL for secp256k1 public keys
     pub const SECP256K1_TYPE_URL: &'static str = "/cosmos.crypto.secp256k1.PubKey";
 
+    /// Protobuf [`Any`] type URL for secp256r1 public keys
+    pub const SECP256R1_TYPE_URL: &'static str = "/cosmos.crypto.secp256r1.PubKey";
+
+    /// Protobuf [`Any`] type URL for Ethermint eth_secp256k1 public keys
+    pub const ETH_SECP256K1_TYPE_URL: &'static str = "/ethermint.crypto.v1.ethsecp256k1.PubKey";
+
+    /// Create an Ethermint eth_secp256k1 [`PublicKey`], as used by EVM-compatible
+    /// chains, from an ECDSA/secp256k1 [`k256::ecdsa::VerifyingKey`].
+    ///
+    /// Its [`AccountId`] is derived like an Ethereum address, and signatures
+    /// are computed over the Keccak-256 digest of the sign bytes.
+    pub fn from_eth_secp256k1(verifying_key: k256::ecdsa::VerifyingKey) -> Self {
+        PublicKey(Inner::EthSecp256k1(verifying_key))
+    }
+
     /// Parse public key from Cosmos JSON format.
     pub fn from_json(s: &str) -> Result<Self> {
         Ok(serde_json::from_str::<PublicKey>(s)?)
@@ -30,13 +71,132 @@ impl PublicKey {
         serde_json::to_string(&self).expect("JSON serialization error")
     }
 
+    /// Decode a public key from its legacy Amino binary encoding, i.e. its
+    /// `tendermint/PubKeyEd25519` or `tendermint/PubKeySecp256k1` prefix
+    /// followed by its length-prefixed key bytes.
+    pub fn from_amino_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < AMINO_ED25519_PREFIX.len() {
+            return Err(Error::Crypto).wrap_err("truncated Amino public key");
+        }
+
+        let (prefix, mut key) = bytes.split_at(AMINO_ED25519_PREFIX.len());
+
+        let type_url = match prefix {
+            p if p == AMINO_ED25519_PREFIX => Self::ED25519_TYPE_URL,
+            p if p == AMINO_SECP256K1_PREFIX => Self::SECP256K1_TYPE_URL,
+            _ => {
+                return Err(Error::Crypto)
+                    .wrap_err_with(|| format!("unknown Amino public key prefix: {:02x?}", prefix))
+            }
+        };
+
+        let len = prost::encoding::decode_varint(&mut key)?;
+
+        if len != key.len() as u64 {
+            return Err(Error::Crypto).wrap_err_with(|| {
+                format!("invalid length for Amino {} public key: {}", type_url, len)
+            });
+        }
+
+        Self::from_raw(type_url, key)
+    }
+
+    /// Encode this public key with the legacy Amino binary encoding.
+    ///
+    /// Only Ed25519 and secp256k1 keys have an Amino encoding.
+    pub fn to_amino_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = match &self.0 {
+            Inner::Tendermint(tendermint::PublicKey::Ed25519(_)) => AMINO_ED25519_PREFIX.to_vec(),
+            Inner::Tendermint(tendermint::PublicKey::Secp256k1(_)) => {
+                AMINO_SECP256K1_PREFIX.to_vec()
+            }
+            _ => {
+                return Err(Error::Crypto).wrap_err_with(|| {
+                    format!("no Amino encoding for {} public keys", self.type_url())
+                })
+            }
+        };
+
+        let key = self.to_bytes();
+        prost::encoding::encode_varint(key.len() as u64, &mut bytes);
+        bytes.extend_from_slice(&key);
+        Ok(bytes)
+    }
+
+    /// Parse a public key from the legacy Bech32 format, i.e. the Bech32
+    /// encoding of its Amino binary encoding, as used by e.g. `cosmospub1...`
+    /// and `cosmosvalconspub1...` strings.
+    ///
+    /// The human-readable prefix is not checked.
+    pub fn from_bech32(s: &str) -> Result<Self> {
+        let (_, bytes) = bech32::decode(s).wrap_err_with(|| format!("invalid bech32: '{}'", s))?;
+        Self::from_amino_bytes(&bytes)
+    }
+
+    /// Serialize this public key in the legacy Bech32 format with the given
+    /// human-readable prefix, e.g. `cosmospub` or `cosmosvalconspub`.
+    pub fn to_bech32(&self, prefix: &str) -> Result<String> {
+        Ok(bech32::encode(prefix, self.to_amino_bytes()?))
+    }
+
+    /// Parse a public key from its raw bytes, given its type URL.
+    pub fn from_raw(type_url: &str, bytes: &[u8]) -> Result<Self> {
+        let public_key = match type_url {
+            Self::ED25519_TYPE_URL => {
+                tendermint::PublicKey::from_raw_ed25519(bytes).map(Into::into)
+            }
+            Self::SECP256K1_TYPE_URL => {
+                tendermint::PublicKey::from_raw_secp256k1(bytes).map(Into::into)
+            }
+            Self::SECP256R1_TYPE_URL => p256::ecdsa::VerifyingKey::from_sec1_bytes(bytes)
+                .ok()
+                .map(Into::into),
+            Self::ETH_SECP256K1_TYPE_URL => k256::ecdsa::VerifyingKey::from_sec1_bytes(bytes)
+                .ok()
+                .map(Self::from_eth_secp256k1),
+            other => {
+                return Err(Error::Crypto)
+                    .wrap_err_with(|| format!("invalid type URL for public key: {}", other))
+            }
+        };
+
+        public_key
+            .ok_or(Error::Crypto)
+            .wrap_err_with(|| format!("malformed {} public key", type_url))
+    }
+
     /// Get the [`AccountId`] for this [`PublicKey`] (if applicable).
     pub fn account_id(&self, prefix: &str) -> Result<AccountId> {
         match &self.0 {
-            tendermint::PublicKey::Secp256k1(encoded_point) => {
+            Inner::Tendermint(tendermint::PublicKey::Secp256k1(encoded_point)) => {
                 let id = tendermint::account::Id::from(*encoded_point);
                 AccountId::new(prefix, id.as_bytes())
             }
+            Inner::Tendermint(tendermint::PublicKey::Ed25519(public_key)) => {
+                // Ed25519 account IDs are the first 20 bytes of the SHA-256 of the key
+                let digest = Sha256::digest(public_key.as_bytes());
+                AccountId::new(prefix, &digest[..tendermint::account::LENGTH])
+            }
+            Inner::Secp256r1(_) => {
+                // secp256r1 account IDs are derived using the Cosmos SDK's
+                // `address.Hash` function (ADR-028) with the key's Protobuf
+                // message name as the type, i.e.
+                // `SHA-256(SHA-256("cosmos.crypto.secp256r1.PubKey") || key)`
+                let type_hash = Sha256::digest(b"cosmos.crypto.secp256r1.PubKey");
+                let digest = Sha256::new()
+                    .chain(type_hash)
+                    .chain(self.to_bytes())
+                    .finalize();
+
+                AccountId::new(prefix, &digest)
+            }
+            Inner::EthSecp256k1(verifying_key) => {
+                // Ethereum addresses are the last 20 bytes of the Keccak-256 of
+                // the uncompressed key, excluding its SEC1 tag byte
+                let encoded_point = verifying_key.to_encoded_point(false);
+                let digest = Keccak256::digest(&encoded_point.as_bytes()[1..]);
+                AccountId::new(prefix, &digest[12..])
+            }
             _ => Err(Error::Crypto.into()),
         }
     }
@@ -44,8 +204,10 @@ impl PublicKey {
     /// Get the type URL for this [`PublicKey`].
     pub fn type_url(&self) -> &'static str {
         match &self.0 {
-            tendermint::PublicKey::Ed25519(_) => Self::ED25519_TYPE_URL,
-            tendermint::PublicKey::Secp256k1(_) => Self::SECP256K1_TYPE_URL,
+            Inner::Tendermint(tendermint::PublicKey::Ed25519(_)) => Self::ED25519_TYPE_URL,
+            Inner::Tendermint(tendermint::PublicKey::Secp256k1(_)) => Self::SECP256K1_TYPE_URL,
+            Inner::Secp256r1(_) => Self::SECP256R1_TYPE_URL,
+            Inner::EthSecp256k1(_) => Self::ETH_SECP256K1_TYPE_URL,
             // `tendermint::PublicKey` is `non_exhaustive`
             _ => unreachable!("unknown pubic key type"),
         }
@@ -54,11 +216,24 @@ impl PublicKey {
     /// Convert this [`PublicKey`] to a Protobuf [`Any`] type.
     pub fn to_any(&self) -> Result<Any> {
         let value = match self.0 {
-            tendermint::PublicKey::Ed25519(_) => proto::cosmos::crypto::secp256k1::PubKey {
+            Inner::Tendermint(tendermint::PublicKey::Ed25519(_)) => {
+                proto::cosmos::crypto::ed25519::PubKey {
+                    key: self.to_bytes(),
+                }
+                .to_bytes()
+            }
+            Inner::Tendermint(tendermint::PublicKey::Secp256k1(_)) => {
+                proto::cosmos::crypto::secp256k1::PubKey {
+                    key: self.to_bytes(),
+                }
+                .to_bytes()
+            }
+            Inner::Secp256r1(_) => proto::cosmos::crypto::secp256r1::PubKey {
                 key: self.to_bytes(),
             }
             .to_bytes(),
-            tendermint::PublicKey::Secp256k1(_) => proto::cosmos::crypto::secp256k1::PubKey {
+            // Ethermint's `PubKey` has the same encoding as the SDK's secp256k1 `PubKey`
+            Inner::EthSecp256k1(_) => proto::cosmos::crypto::secp256k1::PubKey {
                 key: self.to_bytes(),
             }
             .to_bytes(),
@@ -71,15 +246,80 @@ impl PublicKey {
         })
     }
 
+    /// Get the [`tendermint::PublicKey`] for this [`PublicKey`], if it's a
+    /// key type supported by Tendermint (i.e. Ed25519 or secp256k1).
+    pub fn to_tendermint(&self) -> Option<tendermint::PublicKey> {
+        match &self.0 {
+            Inner::Tendermint(public_key) => Some(*public_key),
+            _ => None,
+        }
+    }
+
     /// Serialize this [`PublicKey`] as a byte vector.
+    ///
+    /// ECDSA keys are serialized as compressed SEC1 points.
     pub fn to_bytes(&self) -> Vec<u8> {
-        self.0.to_bytes()
+        match &self.0 {
+            Inner::Tendermint(public_key) => public_key.to_bytes(),
+            Inner::Secp256r1(verifying_key) => {
+                verifying_key.to_encoded_point(true).as_bytes().to_vec()
+            }
+            Inner::EthSecp256k1(verifying_key) => {
+                verifying_key.to_encoded_point(true).as_bytes().to_vec()
+            }
+        }
+    }
+
+    /// Verify the given signature over the given message using this [`PublicKey`].
+    ///
+    /// Like the Cosmos SDK, ECDSA signatures must be 64-byte `r || s` values
+    /// with a normalized (i.e. "low") `s` component. Ethermint eth_secp256k1
+    /// signatures may additionally include a trailing recovery ID.
+    pub fn verify(&self, msg: &[u8], signature: &[u8]) -> Result<()> {
+        match &self.0 {
+            Inner::Tendermint(public_key) => {
+                let signature = tendermint::Signature::try_from(signature)
+                    .or(Err(Error::Signature))
+                    .wrap_err("malformed signature")?;
+
+                public_key.verify(msg, &signature).or(Err(Error::Signature))
+            }
+            Inner::Secp256r1(verifying_key) => {
+                let signature = p256::ecdsa::Signature::try_from(signature)
+                    .or(Err(Error::Signature))
+                    .wrap_err("malformed signature")?;
+
+                if signature.normalize_s().is_some() {
+                    return Err(Error::Signature).wrap_err("signature has a high S value");
+                }
+
+                verifying_key
+                    .verify(msg, &signature)
+                    .or(Err(Error::Signature))
+            }
+            Inner::EthSecp256k1(verifying_key) => {
+                // Ethermint signatures include a trailing recovery ID
+                let signature = match signature.len() {
+                    k256::ecdsa::recoverable::SIZE => &signature[..64],
+                    _ => signature,
+                };
+
+                let signature = k256::ecdsa::Signature::try_from(signature)
+                    .or(Err(Error::Signature))
+                    .wrap_err("malformed signature")?;
+
+                verifying_key
+                    .verify_digest(Keccak256::new().chain(msg), &signature)
+                    .or(Err(Error::Signature))
+            }
+        }
+        .wrap_err_with(|| format!("{} signature verification failed", self.type_url()))
     }
 }
 
 impl From<k256::ecdsa::VerifyingKey> for PublicKey {
     fn from(vk: k256::ecdsa::VerifyingKey) -> PublicKey {
-        PublicKey(vk.into())
+        PublicKey(Inner::Tendermint(vk.into()))
     }
 }
 
@@ -89,6 +329,18 @@ impl From<&k256::ecdsa::VerifyingKey> for PublicKey {
     }
 }
 
+impl From<p256::ecdsa::VerifyingKey> for PublicKey {
+    fn from(vk: p256::ecdsa::VerifyingKey) -> PublicKey {
+        PublicKey(Inner::Secp256r1(vk))
+    }
+}
+
+impl From<&p256::ecdsa::VerifyingKey> for PublicKey {
+    fn from(vk: &p256::ecdsa::VerifyingKey) -> PublicKey {
+        PublicKey::from(*vk)
+    }
+}
+
 impl TryFrom<Any> for PublicKey {
     type Error = ErrorReport;
 
@@ -108,6 +360,14 @@ impl TryFrom<&Any> for PublicKey {
             Self::SECP256K1_TYPE_URL => {
                 proto::cosmos::crypto::secp256k1::PubKey::decode(&*any.value)?.try_into()
             }
+            Self::SECP256R1_TYPE_URL => {
+                proto::cosmos::crypto::secp256r1::PubKey::decode(&*any.value)?.try_into()
+            }
+            // Ethermint's `PubKey` has the same encoding as the SDK's secp256k1 `PubKey`
+            Self::ETH_SECP256K1_TYPE_URL => {
+                let public_key = proto::cosmos::crypto::secp256k1::PubKey::decode(&*any.value)?;
+                PublicKey::from_raw(Self::ETH_SECP256K1_TYPE_URL, &public_key.key)
+            }
             other => Err(Error::Crypto)
                 .wrap_err_with(|| format!("invalid type URL for public key: {}", other)),
         }
@@ -134,6 +394,14 @@ impl TryFrom<proto::cosmos::crypto::secp256k1::PubKey> for PublicKey {
     }
 }
 
+impl TryFrom<proto::cosmos::crypto::secp256r1::PubKey> for PublicKey {
+    type Error = ErrorReport;
+
+    fn try_from(public_key: proto::cosmos::crypto::secp256r1::PubKey) -> Result<PublicKey> {
+        PublicKey::from_raw(Self::SECP256R1_TYPE_URL, &public_key.key)
+    }
+}
+
 impl From<PublicKey> for Any {
     fn from(public_key: PublicKey) -> Any {
         // This is largely a workaround for `tendermint::PublicKey` being
@@ -144,13 +412,17 @@ impl From<PublicKey> for Any {
 
 impl From<tendermint::PublicKey> for PublicKey {
     fn from(pk: tendermint::PublicKey) -> PublicKey {
-        PublicKey(pk)
+        PublicKey(Inner::Tendermint(pk))
     }
 }
 
-impl From<PublicKey> for tendermint::PublicKey {
-    fn from(pk: PublicKey) -> tendermint::PublicKey {
-        pk.0
+impl TryFrom<PublicKey> for tendermint::PublicKey {
+    type Error = ErrorReport;
+
+    fn try_from(pk: PublicKey) -> Result<tendermint::PublicKey> {
+        pk.to_tendermint()
+            .ok_or(Error::Crypto)
+            .wrap_err_with(|| format!("{} keys aren't supported by Tendermint", pk.type_url()))
     }
 }
 
@@ -208,23 +480,16 @@ impl TryFrom<&PublicKeyJson> for PublicKey {
 
     fn try_from(json: &PublicKeyJson) -> Result<PublicKey> {
         let pk_bytes = base64::decode(&json.key)?;
-
-        let tm_key = match json.type_url.as_str() {
-            Self::ED25519_TYPE_URL => tendermint::PublicKey::from_raw_ed25519(&pk_bytes),
-            Self::SECP256K1_TYPE_URL => tendermint::PublicKey::from_raw_secp256k1(&pk_bytes),
-            other => {
-                return Err(Error::Crypto)
-                    .wrap_err_with(|| format!("invalid public key @type: {}", other))
-            }
-        };
-
-        tm_key.map(Into::into).ok_or_else(|| Error::Crypto.into())
+        PublicKey::from_raw(&json.type_url, &pk_bytes)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::PublicKey;
+    use crate::{crypto::secp256r1, Any};
+    use hex_literal::hex;
+    use p256::elliptic_curve::{ff::PrimeField, FieldBytes};
 
     const EXAMPLE_JSON: &str = "{\"@type\":\"/cosmos.crypto.ed25519.PubKey\",\"key\":\"sEEsVGkXvyewKLWMJbHVDRkBoerW0IIwmj1rHkabtHU=\"}";
 
@@ -247,4 +512,112 @@ mod tests {
         );
         assert_eq!(EXAMPLE_JSON, example_key.to_string());
     }
+
+    #[test]
+    fn secp256r1_round_trip() {
+        // Private key `1`, i.e. the public key is the P-256 generator point
+        let mut secret_key = [0u8; 32];
+        secret_key[31] = 1;
+
+        let signing_key = secp256r1::SigningKey::from_bytes(&secret_key).unwrap();
+        let public_key = signing_key.public_key();
+
+        assert_eq!(
+            public_key.to_bytes(),
+            hex!("036B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C296")
+        );
+        assert_eq!(
+            public_key.to_json(),
+            "{\"@type\":\"/cosmos.crypto.secp256r1.PubKey\",\"key\":\"A2sX0fLhLEJH+Lzm5WOkQPJ3A32BLeszoPShOUXYmMKW\"}"
+        );
+        assert_eq!(
+            public_key.to_json().parse::<PublicKey>().unwrap(),
+            public_key
+        );
+        assert_eq!(
+            PublicKey::try_from(Any::from(public_key)).unwrap(),
+            public_key
+        );
+        assert_eq!(
+            public_key.account_id("cosmos").unwrap().as_ref(),
+            "cosmos1552pdl8c2rns85k4rz8kzkn579q605epn7486w37w4pk8tyh30ws2tr56c"
+        );
+        assert_eq!(public_key.to_tendermint(), None);
+        assert!(tendermint::PublicKey::try_from(public_key).is_err());
+    }
+
+    #[test]
+    fn secp256r1_verify() {
+        let signing_key = secp256r1::SigningKey::random();
+        let public_key = signing_key.public_key();
+        let signature = signing_key.sign(b"sign bytes").unwrap();
+
+        public_key
+            .verify(b"sign bytes", signature.as_ref())
+            .unwrap();
+        assert!(public_key
+            .verify(b"other bytes", signature.as_ref())
+            .is_err());
+
+        // The same signature with a high `s` value is rejected
+        let (r, s) = signature.split_scalars();
+        let high_s = secp256r1::Signature::from_scalars(
+            FieldBytes::<p256::NistP256>::from(r),
+            (-*s).to_repr(),
+        )
+        .unwrap();
+
+        assert!(public_key.verify(b"sign bytes", high_s.as_ref()).is_err());
+    }
+
+    #[test]
+    fn amino_secp256k1() {
+        let public_key = PublicKey::from_raw(
+            PublicKey::SECP256K1_TYPE_URL,
+            &hex!("034f04181eeba35391b858633a765c4a0c189697b40d216354d50890d350c70290"),
+        )
+        .unwrap();
+
+        let amino_bytes = public_key.to_amino_bytes().unwrap();
+        assert_eq!(
+            amino_bytes,
+            hex!("eb5ae98721034f04181eeba35391b858633a765c4a0c189697b40d216354d50890d350c70290")
+        );
+        assert_eq!(
+            PublicKey::from_amino_bytes(&amino_bytes).unwrap(),
+            public_key
+        );
+
+        let bech32 =
+            "cosmospub1addwnpepqd8sgxq7aw348ydctp3n5ajufgxp395hksxjzc6565yfp56scupfqhlgyg5";
+        assert_eq!(public_key.to_bech32("cosmospub").unwrap(), bech32);
+        assert_eq!(PublicKey::from_bech32(bech32).unwrap(), public_key);
+    }
+
+    #[test]
+    fn amino_ed25519() {
+        let public_key = PublicKey::from_raw(
+            PublicKey::ED25519_TYPE_URL,
+            &hex!("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"),
+        )
+        .unwrap();
+
+        let bech32 =
+            "cosmosvalconspub1zcjduepq6adfsqvzky9t042tlmfujeq88g8wzuhnm2nzxfd0qgdx3ac82ydq22knlp";
+        assert_eq!(public_key.to_bech32("cosmosvalconspub").unwrap(), bech32);
+        assert_eq!(PublicKey::from_bech32(bech32).unwrap(), public_key);
+    }
+
+    #[test]
+    fn amino_malformed() {
+        // Unknown prefix
+        assert!(PublicKey::from_amino_bytes(&hex!("0102030421")).is_err());
+
+        // Length prefix doesn't match key length
+        assert!(PublicKey::from_amino_bytes(&hex!("1624de6421d75a98")).is_err());
+
+        // secp256r1 keys have no Amino encoding
+        let public_key = secp256r1::SigningKey::random().public_key();
+        assert!(public_key.to_amino_bytes().is_err());
+    }
 }
@@ -0,0 +1,397 @@
+//! Remote signer which signs transactions using a simple HTTP signing protocol.
+//!
+//! This is a reference [`AsyncTxSigner`] implementation for signing with keys
+//! held by a remote service, e.g. a signing microservice in front of a KMS or
+//! a Vault transit engine.
+//!
+//! # Protocol
+//!
+//! The signing service exposes the following endpoints relative to its base
+//! URL, all of which use JSON request and response bodies:
+//!
+//! - `GET /public_key`: returns `{"public_key": <public key>}`, where the
+//!   public key is encoded as Protobuf JSON, e.g.
+//!   `{"@type": "/cosmos.crypto.secp256k1.PubKey", "key": "<base64>"}`
+//! - `POST /sign`: given `{"sign_bytes": "<base64>"}`, signs the sign bytes
+//!   and returns `{"signature": "<base64>"}`, where the signature is encoded
+//!   as it is in a transaction
+//!
+//! Signatures returned by the service are verified against its public key.
+//!
+//! Note that only plaintext HTTP is supported, so the service should either be
+//! reachable over a trusted network (e.g. `localhost`) or behind a TLS proxy.
+
+use crate::{
+    crypto::{AsyncTxSigner, PublicKey, SignTxFuture},
+    tx::SignatureBytes,
+    Error, Result,
+};
+use eyre::WrapErr;
+use hyper::{
+    body::{self, Buf},
+    client::HttpConnector,
+    header, Body, Client, Method, Request, Uri,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::time::Duration;
+
+/// Path of the public key endpoint.
+const PUBLIC_KEY_PATH: &str = "public_key";
+
+/// Path of the signing endpoint.
+const SIGN_PATH: &str = "sign";
+
+/// Default timeout for requests to the signing service.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// [`AsyncTxSigner`] which signs using a remote signing service.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+/// use cosmrs::{crypto::remote::RemoteSigner, tx::SignDoc};
+/// # use cosmrs::{tx::{Body, Fee, SignerInfo}, Any, Coin};
+///
+/// let signer = RemoteSigner::connect("http://127.0.0.1:8080").await?;
+/// let account_id = signer.public_key().account_id("cosmos")?;
+/// # let body = Body::new(Vec::<Any>::new(), "", 0u32);
+/// # let fee = Fee::from_amount_and_gas(
+/// #     Coin { denom: "uatom".parse()?, amount: 1000u64.into() },
+/// #     100_000u64,
+/// # );
+/// # let auth_info = SignerInfo::single_direct(Some(signer.public_key()), 0).auth_info(fee);
+/// # let sign_doc = SignDoc::new(&body, &auth_info, &"cosmoshub-4".parse()?, 1)?;
+///
+/// let raw = sign_doc.sign_async(&signer).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct RemoteSigner {
+    /// Signing service endpoint.
+    endpoint: Endpoint,
+
+    /// Public key of the remote signing key.
+    public_key: PublicKey,
+}
+
+impl RemoteSigner {
+    /// Connect to the signing service at the given base URL, fetching the
+    /// public key of its signing key.
+    ///
+    /// Requests time out after [`DEFAULT_TIMEOUT`].
+    pub async fn connect(base_url: &str) -> Result<Self> {
+        Self::connect_with_timeout(base_url, DEFAULT_TIMEOUT).await
+    }
+
+    /// Connect to the signing service at the given base URL using the given
+    /// request timeout, fetching the public key of its signing key.
+    pub async fn connect_with_timeout(base_url: &str, timeout: Duration) -> Result<Self> {
+        let endpoint = Endpoint::new(base_url, timeout)?;
+
+        let response: PublicKeyResponse = endpoint
+            .request(Method::GET, PUBLIC_KEY_PATH, Body::empty())
+            .await
+            .wrap_err("error fetching remote signer public key")?;
+
+        Ok(Self {
+            endpoint,
+            public_key: response.public_key,
+        })
+    }
+
+    /// Create a remote signer for the signing service at the given base URL
+    /// whose signing key has the given public key, which is used to verify
+    /// the service's signatures.
+    ///
+    /// Requests time out after [`DEFAULT_TIMEOUT`], which can be changed
+    /// using [`RemoteSigner::timeout`].
+    pub fn new(base_url: &str, public_key: PublicKey) -> Result<Self> {
+        Ok(Self {
+            endpoint: Endpoint::new(base_url, DEFAULT_TIMEOUT)?,
+            public_key,
+        })
+    }
+
+    /// Set the timeout for requests to the signing service.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.endpoint.timeout = timeout;
+        self
+    }
+
+    /// Get the base URL of the signing service.
+    pub fn base_url(&self) -> &str {
+        &self.endpoint.base_url
+    }
+
+    /// Get the [`PublicKey`] of the remote signing key.
+    pub fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    /// Sign the given sign bytes using the signing service, returning the
+    /// signature as it's encoded in a transaction.
+    pub async fn sign(&self, sign_bytes: &[u8]) -> Result<SignatureBytes> {
+        let request = serde_json::to_vec(&SignRequest {
+            sign_bytes: sign_bytes.to_vec(),
+        })?;
+
+        let response: SignResponse = self
+            .endpoint
+            .request(Method::POST, SIGN_PATH, request.into())
+            .await
+            .wrap_err("error signing with remote signer")?;
+
+        self.public_key
+            .verify(sign_bytes, &response.signature)
+            .wrap_err("remote signer returned an invalid signature")?;
+
+        Ok(response.signature)
+    }
+}
+
+impl AsyncTxSigner for RemoteSigner {
+    fn sign_tx_async<'a>(&'a self, sign_bytes: &'a [u8]) -> SignTxFuture<'a> {
+        Box::pin(self.sign(sign_bytes))
+    }
+
+    fn signer_public_key(&self) -> PublicKey {
+        self.public_key
+    }
+}
+
+/// HTTP endpoint of a signing service.
+#[derive(Clone, Debug)]
+struct Endpoint {
+    /// HTTP client.
+    client: Client<HttpConnector>,
+
+    /// Base URL of the signing service, with a trailing slash.
+    base_url: String,
+
+    /// Request timeout.
+    timeout: Duration,
+}
+
+impl Endpoint {
+    /// Create an endpoint for the signing service at the given base URL.
+    fn new(base_url: &str, timeout: Duration) -> Result<Self> {
+        let mut base_url = base_url.to_owned();
+
+        if !base_url.ends_with('/') {
+            base_url.push('/');
+        }
+
+        // Ensure the URL is valid
+        base_url.parse::<Uri>()?;
+
+        Ok(Self {
+            client: Client::new(),
+            base_url,
+            timeout,
+        })
+    }
+
+    /// Send a request to the given endpoint, parsing the JSON response.
+    ///
+    /// Fails if the response isn't received within the endpoint's timeout.
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Body,
+    ) -> Result<T> {
+        let uri = format!("{}{}", self.base_url, path).parse::<Uri>()?;
+        let request = Request::builder()
+            .method(method)
+            .uri(&uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(body)?;
+
+        tokio::time::timeout(self.timeout, self.send(request))
+            .await
+            .wrap_err_with(|| format!("request timed out after {:?}: {}", self.timeout, uri))?
+    }
+
+    /// Send the given request, parsing the JSON response.
+    async fn send<T: DeserializeOwned>(&self, request: Request<Body>) -> Result<T> {
+        let uri = request.uri().clone();
+        let response = self.client.request(request).await?;
+        let status = response.status();
+
+        if !status.is_success() {
+            return Err(Error::RemoteSigner {
+                status: status.as_u16(),
+            })
+            .wrap_err_with(|| format!("request failed: {}", uri));
+        }
+
+        let body = body::aggregate(response.into_body()).await?;
+        Ok(serde_json::from_reader(body.reader())?)
+    }
+}
+
+/// Response of the public key endpoint.
+#[derive(Deserialize, Serialize)]
+struct PublicKeyResponse {
+    /// Public key of the signing key.
+    public_key: PublicKey,
+}
+
+/// Request to the signing endpoint.
+#[derive(Deserialize, Serialize)]
+struct SignRequest {
+    /// Sign bytes to be signed.
+    #[serde(with = "crate::serializers::base64_bytes")]
+    sign_bytes: Vec<u8>,
+}
+
+/// Response of the signing endpoint.
+#[derive(Deserialize, Serialize)]
+struct SignResponse {
+    /// Signature as it's encoded in a transaction.
+    #[serde(with = "crate::serializers::base64_bytes")]
+    signature: SignatureBytes,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PublicKeyResponse, RemoteSigner, SignRequest, SignResponse};
+    use crate::{
+        crypto::{AsyncTxSigner, PublicKey},
+        tx::{Body, Fee, SignDoc, SignerInfo},
+        Coin,
+    };
+    use ecdsa::signature::Signer;
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Body as HyperBody, Method, Request, Response, Server, StatusCode,
+    };
+    use k256::ecdsa::{Signature, SigningKey};
+    use rand_core::OsRng;
+    use std::{convert::Infallible, net::SocketAddr, sync::Arc, time::Duration};
+
+    /// Start a local stand-in signing service, returning its base URL.
+    fn spawn_server(signing_key: SigningKey, tamper: bool) -> String {
+        let signing_key = Arc::new(signing_key);
+
+        let make_service = make_service_fn(move |_| {
+            let signing_key = signing_key.clone();
+
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request: Request<HyperBody>| {
+                    let signing_key = signing_key.clone();
+                    async move { Ok::<_, Infallible>(handle(request, &signing_key, tamper).await) }
+                }))
+            }
+        });
+
+        let server = Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0))).serve(make_service);
+        let base_url = format!("http://{}", server.local_addr());
+        tokio::spawn(server);
+        base_url
+    }
+
+    /// Handle a request to the stand-in signing service.
+    async fn handle(
+        request: Request<HyperBody>,
+        signing_key: &SigningKey,
+        tamper: bool,
+    ) -> Response<HyperBody> {
+        let body = match (request.method(), request.uri().path()) {
+            (&Method::GET, "/slow/public_key") => {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                return Response::new(HyperBody::empty());
+            }
+            (&Method::GET, "/public_key") => serde_json::to_vec(&PublicKeyResponse {
+                public_key: PublicKey::from(signing_key.verifying_key()),
+            }),
+            (&Method::POST, "/sign") => {
+                let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                let request: SignRequest = serde_json::from_slice(&body).unwrap();
+                let signature: Signature = signing_key.sign(&request.sign_bytes);
+                let mut signature = signature.as_ref().to_vec();
+
+                if tamper {
+                    signature[0] ^= 1;
+                }
+
+                serde_json::to_vec(&SignResponse { signature })
+            }
+            _ => {
+                let mut response = Response::new(HyperBody::empty());
+                *response.status_mut() = StatusCode::NOT_FOUND;
+                return response;
+            }
+        };
+
+        Response::new(body.unwrap().into())
+    }
+
+    fn sign_doc(signer: &RemoteSigner) -> SignDoc {
+        let body = Body::new(Vec::<crate::Any>::new(), "remote", 0u32);
+        let fee = Fee::from_amount_and_gas(
+            Coin {
+                denom: "uatom".parse().unwrap(),
+                amount: 1000u64.into(),
+            },
+            100_000u64,
+        );
+        let auth_info =
+            SignerInfo::single_direct(Some(signer.signer_public_key()), 0).auth_info(fee);
+
+        SignDoc::new(&body, &auth_info, &"cosmoshub-4".parse().unwrap(), 1).unwrap()
+    }
+
+    #[tokio::test]
+    async fn sign_doc_with_remote_signer() {
+        let signing_key = SigningKey::random(&mut OsRng);
+        let public_key = PublicKey::from(signing_key.verifying_key());
+        let signer = RemoteSigner::connect(&spawn_server(signing_key, false))
+            .await
+            .unwrap();
+
+        assert_eq!(signer.public_key(), public_key);
+
+        let sign_doc = sign_doc(&signer);
+        let sign_bytes = sign_doc.clone().into_bytes().unwrap();
+        let raw = sign_doc.sign_async(&signer).await.unwrap();
+
+        let signatures = crate::proto::cosmos::tx::v1beta1::TxRaw::from(raw).signatures;
+        public_key.verify(&sign_bytes, &signatures[0]).unwrap();
+    }
+
+    #[tokio::test]
+    async fn reject_invalid_signature() {
+        let signing_key = SigningKey::random(&mut OsRng);
+        let signer = RemoteSigner::connect(&spawn_server(signing_key, true))
+            .await
+            .unwrap();
+
+        assert!(signer.sign(b"example").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn reject_error_status() {
+        let signing_key = SigningKey::random(&mut OsRng);
+        let base_url = format!("{}/missing", spawn_server(signing_key, false));
+        let err = RemoteSigner::connect(&base_url).await.unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<crate::Error>(),
+            Some(crate::Error::RemoteSigner { status: 404 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn timeout() {
+        let signing_key = SigningKey::random(&mut OsRng);
+        let base_url = format!("{}/slow", spawn_server(signing_key, false));
+        let err = RemoteSigner::connect_with_timeout(&base_url, Duration::from_millis(100))
+            .await
+            .unwrap_err();
+
+        assert!(err.downcast_ref::<tokio::time::error::Elapsed>().is_some());
+    }
+}
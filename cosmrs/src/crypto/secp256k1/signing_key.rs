@@ -1,12 +1,20 @@
 //! Transaction signing key
 
 use crate::{
-    crypto::{secp256k1::Signature, PublicKey},
+    crypto::{secp256k1::Signature, PublicKey, TxSigner},
+    tx::SignatureBytes,
     ErrorReport, Result,
 };
+use ecdsa::signature::Signer;
 use k256::ecdsa::VerifyingKey;
 use rand_core::OsRng;
 
+#[cfg(feature = "keyring")]
+use {crate::Error, eyre::WrapErr};
+
+#[cfg(feature = "bip32")]
+use crate::crypto::hd;
+
 /// ECDSA/secp256k1 signing key (i.e. private key)
 ///
 /// This is a wrapper type which supports any pluggable ECDSA/secp256k1 signer
@@ -16,12 +24,27 @@ use rand_core::OsRng;
 /// however it can be instantiated from any compatible signer (e.g. HSM, KMS,
 /// etc) by using [`SigningKey::new`].
 ///
+/// The private key of a signing key created from a raw private key (e.g. with
+/// [`SigningKey::from_bytes`] or [`SigningKey::random`]) can be exported, while
+/// the private key of an external signer can't.
+///
 /// Supported alternative signer implementations:
+/// - [`pkcs11::Signer`][`crate::crypto::pkcs11::Signer`]: PKCS#11-backed
+///   ECDSA/secp256k1 signer (requires the `pkcs11` feature)
 /// - [`yubihsm::ecdsa::secp256k1::Signer`]: YubiHSM-backed ECDSA/secp256k1 signer
 ///
 /// [`yubihsm::ecdsa::secp256k1::Signer`]: https://docs.rs/yubihsm/latest/yubihsm/ecdsa/secp256k1/type.Signer.html
 pub struct SigningKey {
-    inner: Box<dyn EcdsaSigner>,
+    inner: Inner,
+}
+
+/// Signer implementation backing a [`SigningKey`].
+enum Inner {
+    /// Software signer, whose private key is held in memory.
+    Software(k256::ecdsa::SigningKey),
+
+    /// External signer, e.g. an HSM.
+    External(Box<dyn EcdsaSigner>),
 }
 
 impl SigningKey {
@@ -29,18 +52,20 @@ impl SigningKey {
     ///
     /// Use [`SigningKey::from_bytes`] to initialize from a raw private key.
     pub fn new(signer: Box<dyn EcdsaSigner>) -> Self {
-        Self { inner: signer }
+        Self {
+            inner: Inner::External(signer),
+        }
     }
 
     /// Initialize from a raw scalar value (big endian).
     pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
         let signing_key = k256::ecdsa::SigningKey::from_bytes(bytes)?;
-        Ok(Self::new(Box::new(signing_key)))
+        Ok(signing_key.into())
     }
 
     /// Generate a random signing key.
     pub fn random() -> Self {
-        Self::new(Box::new(k256::ecdsa::SigningKey::random(&mut OsRng)))
+        k256::ecdsa::SigningKey::random(&mut OsRng).into()
     }
 
     /// Derive a signing key from a [`bip32::DerivationPath`].
@@ -56,14 +81,79 @@ impl SigningKey {
         bip32::XPrv::derive_from_path(seed, path).map(Into::into)
     }
 
+    /// Derive a signing key from a BIP-39 mnemonic phrase and passphrase
+    /// (which is empty by default) using the given [`bip32::DerivationPath`].
+    ///
+    /// If no path is given, the default path `m/44'/118'/0'/0/0` is used.
+    /// Paths for other coin types and accounts can be computed using
+    /// [`hd::derivation_path`].
+    #[cfg(feature = "bip32")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bip32")))]
+    pub fn from_mnemonic(
+        phrase: &str,
+        passphrase: &str,
+        path: Option<&bip32::DerivationPath>,
+    ) -> Result<Self> {
+        let seed = hd::mnemonic_to_seed(phrase, passphrase)?;
+        let path = match path {
+            Some(path) => path.clone(),
+            None => hd::default_path(hd::COSMOS_COIN_TYPE)?,
+        };
+
+        Ok(bip32::XPrv::derive_from_path(seed.as_bytes(), &path)?.into())
+    }
+
+    /// Import a signing key from an ASCII-armored private key encrypted with the
+    /// given passphrase, e.g. one exported with `gaiad keys export`.
+    #[cfg(feature = "keyring")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "keyring")))]
+    pub fn from_armor(armored: &str, passphrase: &str) -> Result<Self> {
+        Self::from_bytes(&crate::keyring::armor::decrypt(armored, passphrase)?)
+    }
+
+    /// Export this signing key as an ASCII-armored private key encrypted with
+    /// the given passphrase, which can be imported with `gaiad keys import`.
+    ///
+    /// Returns an error if this signing key is backed by an external signer
+    /// (i.e. it was created with [`SigningKey::new`]), whose private key can't
+    /// be exported.
+    #[cfg(feature = "keyring")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "keyring")))]
+    pub fn to_armor(&self, passphrase: &str) -> Result<String> {
+        match &self.inner {
+            Inner::Software(signing_key) => {
+                crate::keyring::armor::encrypt(&signing_key.to_bytes(), passphrase)
+            }
+            Inner::External(_) => {
+                Err(Error::Crypto).wrap_err("can't export the private key of an external signer")
+            }
+        }
+    }
+
     /// Sign the given message, returning a signature.
     pub fn sign(&self, msg: &[u8]) -> Result<Signature> {
-        Ok(self.inner.try_sign(msg)?)
+        let signature = match &self.inner {
+            Inner::Software(signing_key) => signing_key.try_sign(msg)?,
+            Inner::External(signer) => signer.try_sign(msg)?,
+        };
+
+        Ok(signature)
     }
 
     /// Get the [`PublicKey`] for this [`SigningKey`].
     pub fn public_key(&self) -> PublicKey {
-        self.inner.verifying_key().into()
+        match &self.inner {
+            Inner::Software(signing_key) => signing_key.verifying_key().into(),
+            Inner::External(signer) => signer.verifying_key().into(),
+        }
+    }
+}
+
+impl From<k256::ecdsa::SigningKey> for SigningKey {
+    fn from(signing_key: k256::ecdsa::SigningKey) -> Self {
+        Self {
+            inner: Inner::Software(signing_key),
+        }
     }
 }
 
@@ -81,6 +171,16 @@ impl TryFrom<&[u8]> for SigningKey {
     }
 }
 
+impl TxSigner for SigningKey {
+    fn sign_tx(&self, sign_bytes: &[u8]) -> Result<SignatureBytes> {
+        Ok(self.sign(sign_bytes)?.as_ref().to_vec())
+    }
+
+    fn public_key(&self) -> PublicKey {
+        SigningKey::public_key(self)
+    }
+}
+
 #[cfg(feature = "bip32")]
 #[cfg_attr(docsrs, doc(cfg(feature = "bip32")))]
 impl From<bip32::XPrv> for SigningKey {
@@ -93,9 +193,7 @@ impl From<bip32::XPrv> for SigningKey {
 #[cfg_attr(docsrs, doc(cfg(feature = "bip32")))]
 impl From<&bip32::XPrv> for SigningKey {
     fn from(xprv: &bip32::XPrv) -> SigningKey {
-        Self {
-            inner: Box::new(xprv.private_key().clone()),
-        }
+        xprv.private_key().clone().into()
     }
 }
 
@@ -121,3 +219,30 @@ where
         self.into()
     }
 }
+
+#[cfg(all(test, feature = "keyring"))]
+mod tests {
+    use super::SigningKey;
+
+    #[test]
+    fn armor_round_trip() {
+        let signing_key = SigningKey::random();
+        let armored = signing_key.to_armor("passphrase").unwrap();
+
+        assert_eq!(
+            SigningKey::from_armor(&armored, "passphrase")
+                .unwrap()
+                .public_key(),
+            signing_key.public_key()
+        );
+        assert!(SigningKey::from_armor(&armored, "wrong passphrase").is_err());
+    }
+
+    #[test]
+    fn armor_external_signer() {
+        let signing_key = SigningKey::new(Box::new(k256::ecdsa::SigningKey::random(
+            &mut rand_core::OsRng,
+        )));
+        assert!(signing_key.to_armor("passphrase").is_err());
+    }
+}
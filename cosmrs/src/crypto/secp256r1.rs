@@ -0,0 +1,6 @@
+//! ECDSA/secp256r1 (P-256) support
+
+mod signing_key;
+
+pub use self::signing_key::{EcdsaSigner, SigningKey};
+pub use p256::ecdsa::{Signature, VerifyingKey};
@@ -0,0 +1,102 @@
+//! Transaction signing key
+
+use crate::{
+    crypto::{secp256r1::Signature, PublicKey, TxSigner},
+    tx::SignatureBytes,
+    ErrorReport, Result,
+};
+use p256::ecdsa::VerifyingKey;
+use rand_core::OsRng;
+
+/// ECDSA/secp256r1 (P-256) signing key (i.e. private key)
+///
+/// This is a wrapper type which supports any pluggable ECDSA/secp256r1 signer
+/// implementation which impls the [`EcdsaSigner`] trait.
+///
+/// By default it uses [`p256::ecdsa::SigningKey`] as the signer implementation,
+/// however it can be instantiated from any compatible signer (e.g. HSM, secure
+/// enclave, etc) by using [`SigningKey::new`].
+///
+/// Signatures are normalized to have a "low" `s` value, which the Cosmos SDK
+/// requires when verifying secp256r1 signatures.
+pub struct SigningKey {
+    inner: Box<dyn EcdsaSigner>,
+}
+
+impl SigningKey {
+    /// Initialize from a provided signer object.
+    ///
+    /// Use [`SigningKey::from_bytes`] to initialize from a raw private key.
+    pub fn new(signer: Box<dyn EcdsaSigner>) -> Self {
+        Self { inner: signer }
+    }
+
+    /// Initialize from a raw scalar value (big endian).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let signing_key = p256::ecdsa::SigningKey::from_bytes(bytes)?;
+        Ok(Self::new(Box::new(signing_key)))
+    }
+
+    /// Generate a random signing key.
+    pub fn random() -> Self {
+        Self::new(Box::new(p256::ecdsa::SigningKey::random(&mut OsRng)))
+    }
+
+    /// Sign the given message, returning a signature with a normalized `s`.
+    pub fn sign(&self, msg: &[u8]) -> Result<Signature> {
+        let signature = self.inner.try_sign(msg)?;
+        Ok(signature.normalize_s().unwrap_or(signature))
+    }
+
+    /// Get the [`PublicKey`] for this [`SigningKey`].
+    pub fn public_key(&self) -> PublicKey {
+        self.inner.verifying_key().into()
+    }
+}
+
+impl From<Box<dyn EcdsaSigner>> for SigningKey {
+    fn from(signer: Box<dyn EcdsaSigner>) -> Self {
+        Self::new(signer)
+    }
+}
+
+impl TryFrom<&[u8]> for SigningKey {
+    type Error = ErrorReport;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl TxSigner for SigningKey {
+    fn sign_tx(&self, sign_bytes: &[u8]) -> Result<SignatureBytes> {
+        Ok(self.sign(sign_bytes)?.as_ref().to_vec())
+    }
+
+    fn public_key(&self) -> PublicKey {
+        SigningKey::public_key(self)
+    }
+}
+
+/// ECDSA/secp256r1 signer trait.
+///
+/// This is a trait which enables plugging any backing signing implementation
+/// which produces a compatible [`Signature`] and [`VerifyingKey`].
+///
+/// Note that this trait is bounded on [`ecdsa::signature::Signer`], which is
+/// what is actually used to produce a signature for a given message.
+pub trait EcdsaSigner: ecdsa::signature::Signer<Signature> {
+    /// Get the ECDSA/secp256r1 [`VerifyingKey`] (i.e. public key) which
+    /// which corresponds to this signer's private key.
+    fn verifying_key(&self) -> VerifyingKey;
+}
+
+impl<T> EcdsaSigner for T
+where
+    T: ecdsa::signature::Signer<Signature>,
+    p256::ecdsa::VerifyingKey: for<'a> From<&'a T>,
+{
+    fn verifying_key(&self) -> VerifyingKey {
+        self.into()
+    }
+}
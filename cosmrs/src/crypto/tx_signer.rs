@@ -0,0 +1,19 @@
+//! Transaction signer trait
+
+use crate::{crypto::PublicKey, tx::SignatureBytes, Result};
+
+/// Transaction signer trait.
+///
+/// This is impl'd by the signing keys for all supported key types, e.g.
+/// [`secp256k1::SigningKey`][`crate::crypto::secp256k1::SigningKey`] and
+/// [`ed25519::SigningKey`][`crate::crypto::ed25519::SigningKey`], and allows
+/// signing transactions with any of them, e.g. using
+/// [`SignDoc::sign`][`crate::tx::SignDoc::sign`].
+pub trait TxSigner {
+    /// Sign the given sign bytes, returning the signature as it's encoded
+    /// in a transaction.
+    fn sign_tx(&self, sign_bytes: &[u8]) -> Result<SignatureBytes>;
+
+    /// Get the [`PublicKey`] which verifies this signer's signatures.
+    fn public_key(&self) -> PublicKey;
+}
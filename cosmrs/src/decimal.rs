@@ -3,6 +3,7 @@
 //! [1]: https://pkg.go.dev/github.com/cosmos/cosmos-sdk/types#Dec
 
 use crate::{ErrorReport, Result};
+use serde::{de, de::Error as _, ser, Deserialize, Serialize};
 use std::{
     fmt,
     ops::{Add, AddAssign},
@@ -15,6 +16,13 @@ use std::{
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 pub struct Decimal(u64);
 
+impl Decimal {
+    /// Is this [`Decimal`] zero?
+    pub fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
 impl FromStr for Decimal {
     type Err = ErrorReport;
 
@@ -29,6 +37,20 @@ impl fmt::Display for Decimal {
     }
 }
 
+impl<'de> Deserialize<'de> for Decimal {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for Decimal {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_string().serialize(serializer)
+    }
+}
+
 impl Add for Decimal {
     type Output = Decimal;
 
@@ -2,10 +2,18 @@
 //!
 //! <https://docs.cosmos.network/master/modules/distribution/>
 
-use crate::{proto, tx::Msg, AccountId, Coin, ErrorReport, Result};
+use crate::{
+    base::validate_coins,
+    proto,
+    tx::{LegacyAminoMsg, Msg},
+    AccountId, Coin, Error, ErrorReport, Result,
+};
+use eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
 
 /// MsgSetWithdrawAddress represents a message to set a withdraw address for staking rewards.
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, PartialOrd, Ord, Serialize)]
 pub struct MsgSetWithdrawAddress {
     /// Delegator's address.
     pub delegator_address: AccountId,
@@ -16,6 +24,21 @@ pub struct MsgSetWithdrawAddress {
 
 impl Msg for MsgSetWithdrawAddress {
     type Proto = proto::cosmos::distribution::v1beta1::MsgSetWithdrawAddress;
+
+    fn signers(&self) -> Result<Vec<AccountId>> {
+        Ok(vec![self.delegator_address.clone()])
+    }
+}
+
+impl LegacyAminoMsg for MsgSetWithdrawAddress {
+    const AMINO_TYPE: &'static str = "cosmos-sdk/MsgModifyWithdrawAddress";
+
+    fn to_amino_value(&self) -> Result<serde_json::Value> {
+        Ok(json!({
+            "delegator_address": self.delegator_address,
+            "withdraw_address": self.withdraw_address,
+        }))
+    }
 }
 
 impl TryFrom<proto::cosmos::distribution::v1beta1::MsgSetWithdrawAddress>
@@ -65,7 +88,7 @@ impl From<&MsgSetWithdrawAddress> for proto::cosmos::distribution::v1beta1::MsgS
 }
 
 /// MsgWithdrawDelegatorReward represents a message to withdraw a delegator's reward from a validator.
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, PartialOrd, Ord, Serialize)]
 pub struct MsgWithdrawDelegatorReward {
     /// Delegator's address.
     pub delegator_address: AccountId,
@@ -76,6 +99,21 @@ pub struct MsgWithdrawDelegatorReward {
 
 impl Msg for MsgWithdrawDelegatorReward {
     type Proto = proto::cosmos::distribution::v1beta1::MsgWithdrawDelegatorReward;
+
+    fn signers(&self) -> Result<Vec<AccountId>> {
+        Ok(vec![self.delegator_address.clone()])
+    }
+}
+
+impl LegacyAminoMsg for MsgWithdrawDelegatorReward {
+    const AMINO_TYPE: &'static str = "cosmos-sdk/MsgWithdrawDelegationReward";
+
+    fn to_amino_value(&self) -> Result<serde_json::Value> {
+        Ok(json!({
+            "delegator_address": self.delegator_address,
+            "validator_address": self.validator_address,
+        }))
+    }
 }
 
 impl TryFrom<proto::cosmos::distribution::v1beta1::MsgWithdrawDelegatorReward>
@@ -129,7 +167,7 @@ impl From<&MsgWithdrawDelegatorReward>
 }
 
 /// WithdrawValidatorCommission represents a message to withdraw a validator's staking commission.
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, PartialOrd, Ord, Serialize)]
 pub struct MsgWithdrawValidatorCommission {
     /// Validator's address.
     pub validator_address: AccountId,
@@ -137,6 +175,33 @@ pub struct MsgWithdrawValidatorCommission {
 
 impl Msg for MsgWithdrawValidatorCommission {
     type Proto = proto::cosmos::distribution::v1beta1::MsgWithdrawValidatorCommission;
+
+    fn signers(&self) -> Result<Vec<AccountId>> {
+        // The commission is withdrawn by the validator operator's account,
+        // which has the same address bytes as the validator
+        let prefix = self.validator_address.prefix();
+        let account_prefix = prefix
+            .strip_suffix("valoper")
+            .ok_or_else(|| Error::AccountId {
+                id: self.validator_address.to_string(),
+            })
+            .wrap_err("expected a validator operator address")?;
+
+        Ok(vec![AccountId::new(
+            account_prefix,
+            &self.validator_address.to_bytes(),
+        )?])
+    }
+}
+
+impl LegacyAminoMsg for MsgWithdrawValidatorCommission {
+    const AMINO_TYPE: &'static str = "cosmos-sdk/MsgWithdrawValidatorCommission";
+
+    fn to_amino_value(&self) -> Result<serde_json::Value> {
+        Ok(json!({
+            "validator_address": self.validator_address,
+        }))
+    }
 }
 
 impl TryFrom<proto::cosmos::distribution::v1beta1::MsgWithdrawValidatorCommission>
@@ -188,7 +253,7 @@ impl From<&MsgWithdrawValidatorCommission>
 }
 
 /// MsgFundCommunityPool represents a message to send coins from depositor to the community pool.
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, PartialOrd, Ord, Serialize)]
 pub struct MsgFundCommunityPool {
     /// Depositor's address.
     pub depositor: AccountId,
@@ -199,6 +264,25 @@ pub struct MsgFundCommunityPool {
 
 impl Msg for MsgFundCommunityPool {
     type Proto = proto::cosmos::distribution::v1beta1::MsgFundCommunityPool;
+
+    fn signers(&self) -> Result<Vec<AccountId>> {
+        Ok(vec![self.depositor.clone()])
+    }
+
+    fn validate_basic(&self) -> Result<()> {
+        validate_coins(&self.amount).wrap_err("invalid amount")
+    }
+}
+
+impl LegacyAminoMsg for MsgFundCommunityPool {
+    const AMINO_TYPE: &'static str = "cosmos-sdk/MsgFundCommunityPool";
+
+    fn to_amino_value(&self) -> Result<serde_json::Value> {
+        Ok(json!({
+            "amount": self.amount,
+            "depositor": self.depositor,
+        }))
+    }
 }
 
 impl TryFrom<proto::cosmos::distribution::v1beta1::MsgFundCommunityPool> for MsgFundCommunityPool {
@@ -251,8 +335,36 @@ impl From<&MsgFundCommunityPool> for proto::cosmos::distribution::v1beta1::MsgFu
             })
         }
         proto::cosmos::distribution::v1beta1::MsgFundCommunityPool {
-            depositor: "".to_string(),
+            depositor: msg.depositor.to_string(),
             amount: amounts,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::MsgFundCommunityPool;
+    use crate::{proto, tx::Msg, Coin};
+
+    #[test]
+    fn fund_community_pool_round_trip() {
+        let msg = MsgFundCommunityPool {
+            depositor: "cosmos19dyl0uyzes4k23lscla02n06fc22h4uqsdwq6z"
+                .parse()
+                .unwrap(),
+            amount: vec![Coin {
+                denom: "uatom".parse().unwrap(),
+                amount: 1000u64.into(),
+            }],
+        };
+
+        let proto = proto::cosmos::distribution::v1beta1::MsgFundCommunityPool::from(&msg);
+        assert_eq!(
+            proto.depositor,
+            "cosmos19dyl0uyzes4k23lscla02n06fc22h4uqsdwq6z"
+        );
+
+        let any = msg.to_any().unwrap();
+        assert_eq!(MsgFundCommunityPool::from_any(&any).unwrap(), msg);
+    }
+}
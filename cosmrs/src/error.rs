@@ -15,6 +15,13 @@ pub enum Error {
         id: String,
     },
 
+    /// Invalid coins.
+    #[error("invalid coins: {coins:?}")]
+    Coins {
+        /// Invalid coins
+        coins: String,
+    },
+
     /// Cryptographic errors.
     #[error("cryptographic error")]
     Crypto,
@@ -33,6 +40,61 @@ pub enum Error {
         name: String,
     },
 
+    /// Signer appears more than once in a transaction.
+    #[error("duplicate signer: {index}")]
+    DuplicateSigner {
+        /// Index of the duplicate signer info.
+        index: usize,
+    },
+
+    /// Unexpected event type.
+    #[error("unexpected event type: {found:?}, expected {expected:?}")]
+    EventType {
+        /// Expected event type.
+        expected: &'static str,
+
+        /// Actual event type found.
+        found: String,
+    },
+
+    /// Invalid gas limit.
+    #[error("invalid gas limit: {gas_limit}")]
+    Gas {
+        /// Invalid gas limit.
+        gas_limit: u64,
+    },
+
+    /// Message failed validation.
+    #[error("invalid message: {type_url:?}")]
+    InvalidMsg {
+        /// Type URL of the invalid message.
+        type_url: &'static str,
+    },
+
+    /// Key already exists in a keyring.
+    #[error("key already exists: {name:?}")]
+    KeyExists {
+        /// Name of the existing key.
+        name: String,
+    },
+
+    /// Key not found in a keyring.
+    #[error("key not found: {name:?}")]
+    KeyNotFound {
+        /// Name of the key (or address) which wasn't found.
+        name: String,
+    },
+
+    /// Transaction memo is too long.
+    #[error("memo too long: {len} bytes, max {max}")]
+    Memo {
+        /// Length of the memo in bytes.
+        len: usize,
+
+        /// Maximum length of the memo in bytes.
+        max: usize,
+    },
+
     /// Protobuf is missing a field.
     #[error("missing proto field: {name:?}")]
     MissingField {
@@ -50,6 +112,42 @@ pub enum Error {
         found: String,
     },
 
+    /// Transaction has no messages.
+    #[error("transaction has no messages")]
+    NoMessages,
+
+    /// Transaction has no signatures.
+    #[error("transaction has no signatures")]
+    NoSignatures,
+
+    /// Unsupported signing mode.
+    #[error("unsupported sign mode: {sign_mode:?}")]
+    SignMode {
+        /// Signing mode which isn't supported.
+        sign_mode: tx::SignMode,
+    },
+
+    /// Remote signer returned an error.
+    #[error("remote signer error: HTTP status {status}")]
+    RemoteSigner {
+        /// HTTP status code returned by the remote signer.
+        status: u16,
+    },
+
+    /// Invalid signature.
+    #[error("invalid signature")]
+    Signature,
+
+    /// Number of signatures or signers doesn't match what's expected.
+    #[error("wrong number of signers: expected {expected}, found {found}")]
+    SignerCount {
+        /// Expected number of signers.
+        expected: usize,
+
+        /// Actual number of signers found.
+        found: usize,
+    },
+
     /// Transaction not found.
     #[error("transaction not found: {hash:?}")]
     TxNotFound {
@@ -57,6 +155,13 @@ pub enum Error {
         hash: tx::Hash,
     },
 
+    /// Unsupported message type.
+    #[error("unsupported Msg type: {type_url:?}")]
+    UnsupportedMsg {
+        /// Type URL of the unsupported message.
+        type_url: String,
+    },
+
     #[error("invalid proto enum value: {name:?}, value: {found_value:?}")]
     InvalidEnumValue {
         /// Name of the enum field
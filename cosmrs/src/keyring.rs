@@ -0,0 +1,521 @@
+//! Cosmos SDK-compatible keyring.
+//!
+//! Supports reading and writing the directory format used by the Cosmos SDK
+//! `file` keyring backend, e.g. `~/.gaia/keyring-file`, in which each entry
+//! is stored as a passphrase-encrypted JWE file:
+//!
+//! - `<name>.info`: the key itself
+//! - `<hex address>.address`: index from the key's address to its name
+//!
+//! Keys can also be exported and imported as passphrase-encrypted ASCII-armored
+//! private keys, i.e. the format used by `gaiad keys export` and `keys import`.
+//!
+//! Only local secp256k1 keys (i.e. keys created with `keys add` or imported
+//! with `keys import`) are supported.
+//!
+//! Note that the Cosmos SDK additionally stores a bcrypt hash of the keyring
+//! passphrase in a `keyhash` file, which isn't used or written by this module.
+//! When the SDK opens a keyring without one, it prompts for the passphrase
+//! twice and then creates it.
+
+pub(crate) mod armor;
+
+mod info;
+mod jwe;
+
+use crate::{crypto::secp256k1, crypto::PublicKey, AccountId, Error, Result};
+use eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+use subtle_encoding::hex;
+use zeroize::{Zeroize, Zeroizing};
+
+#[cfg(unix)]
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+
+/// Suffix of the keyring entries for keys.
+const INFO_SUFFIX: &str = ".info";
+
+/// Suffix of the keyring entries which index keys by address.
+const ADDRESS_SUFFIX: &str = ".address";
+
+/// Cosmos SDK `file` keyring backend.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+/// use cosmrs::keyring::{FileKeyring, LocalKey};
+///
+/// # let dir = tempfile::tempdir()?;
+/// # let keyring_dir = dir.path();
+/// let keyring = FileKeyring::new(keyring_dir, "passphrase");
+/// keyring.add(&LocalKey::random("validator"))?;
+///
+/// let signing_key = keyring.signing_key("validator")?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct FileKeyring {
+    /// Keyring directory.
+    dir: PathBuf,
+
+    /// Passphrase used to encrypt keyring entries.
+    passphrase: Zeroizing<String>,
+}
+
+impl FileKeyring {
+    /// Name of the `file` keyring directory within a node's home directory.
+    pub const DIR_NAME: &'static str = "keyring-file";
+
+    /// Open the keyring in the given directory, e.g. `~/.gaia/keyring-file`,
+    /// using the given passphrase.
+    ///
+    /// The directory is created when the first key is added, if it doesn't
+    /// already exist.
+    pub fn new(dir: impl Into<PathBuf>, passphrase: impl Into<String>) -> Self {
+        Self {
+            dir: dir.into(),
+            passphrase: Zeroizing::new(passphrase.into()),
+        }
+    }
+
+    /// Get the keyring directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// List the names of all keys in the keyring, in sorted order.
+    pub fn list(&self) -> Result<Vec<String>> {
+        let entries = fs::read_dir(&self.dir)
+            .wrap_err_with(|| format!("error reading keyring: {}", self.dir.display()))?;
+
+        let mut names = Vec::new();
+
+        for entry in entries {
+            let file_name = entry?.file_name();
+
+            if let Some(name) = file_name
+                .to_str()
+                .map(filename_unescape)
+                .and_then(|key| key.strip_suffix(INFO_SUFFIX).map(ToOwned::to_owned))
+            {
+                names.push(name);
+            }
+        }
+
+        names.sort();
+        Ok(names)
+    }
+
+    /// Get the key with the given name.
+    pub fn get(&self, name: &str) -> Result<LocalKey> {
+        let key = format!("{}{}", name, INFO_SUFFIX);
+        let item = self.read_item(&key, name)?;
+
+        let local_key = info::decode(&item.data)
+            .wrap_err_with(|| format!("error decoding keyring entry: {}", key))?;
+
+        if local_key.name != name {
+            return Err(Error::Crypto).wrap_err_with(|| {
+                format!(
+                    "keyring entry name mismatch: expected {:?}, found {:?}",
+                    name, local_key.name
+                )
+            });
+        }
+
+        Ok(local_key)
+    }
+
+    /// Get the key with the given address.
+    pub fn get_by_address(&self, address: &AccountId) -> Result<LocalKey> {
+        let item = self.read_item(&address_key(address), address.as_ref())?;
+
+        let name = String::from_utf8(item.data.clone())?
+            .strip_suffix(INFO_SUFFIX)
+            .map(ToOwned::to_owned)
+            .ok_or(Error::Crypto)
+            .wrap_err("malformed keyring address entry")?;
+
+        self.get(&name)
+    }
+
+    /// Get a [`secp256k1::SigningKey`] for the key with the given name.
+    pub fn signing_key(&self, name: &str) -> Result<secp256k1::SigningKey> {
+        self.get(name)?.signing_key()
+    }
+
+    /// Add a key to the keyring.
+    ///
+    /// Returns an error if a key with the same name or address already exists.
+    pub fn add(&self, local_key: &LocalKey) -> Result<()> {
+        let info_key = format!("{}{}", local_key.name, INFO_SUFFIX);
+        let address_key = address_key(&local_key.account_id("cosmos")?);
+
+        for key in [&info_key, &address_key] {
+            if self.path(key).exists() {
+                return Err(Error::KeyExists {
+                    name: local_key.name.clone(),
+                }
+                .into());
+            }
+        }
+
+        self.create_dir()?;
+        self.write_item(&Item::new(info_key.clone(), &info::encode(local_key)))?;
+        self.write_item(&Item::new(address_key, info_key.as_bytes()))
+    }
+
+    /// Delete the key with the given name from the keyring.
+    pub fn delete(&self, name: &str) -> Result<()> {
+        let local_key = self.get(name)?;
+        let address_path = self.path(&address_key(&local_key.account_id("cosmos")?));
+
+        if address_path.exists() {
+            fs::remove_file(address_path)?;
+        }
+
+        fs::remove_file(self.path(&format!("{}{}", name, INFO_SUFFIX)))?;
+        Ok(())
+    }
+
+    /// Get the path to the file containing the entry with the given key.
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(filename_escape(key))
+    }
+
+    /// Create the keyring directory if it doesn't exist.
+    fn create_dir(&self) -> Result<()> {
+        let mut builder = fs::DirBuilder::new();
+        builder.recursive(true);
+
+        #[cfg(unix)]
+        builder.mode(0o700);
+
+        builder
+            .create(&self.dir)
+            .wrap_err_with(|| format!("error creating keyring: {}", self.dir.display()))
+    }
+
+    /// Read and decrypt the entry with the given key.
+    fn read_item(&self, key: &str, name: &str) -> Result<Item> {
+        let path = self.path(key);
+
+        if !path.exists() {
+            return Err(Error::KeyNotFound {
+                name: name.to_owned(),
+            }
+            .into());
+        }
+
+        let jwe = fs::read_to_string(&path)
+            .wrap_err_with(|| format!("error reading keyring entry: {}", path.display()))?;
+
+        let plaintext = jwe::decrypt(&jwe, &self.passphrase)
+            .wrap_err_with(|| format!("error decrypting keyring entry: {}", key))?;
+
+        let item: Item = serde_json::from_slice(&plaintext)?;
+
+        if item.key != key {
+            return Err(Error::Crypto)
+                .wrap_err_with(|| format!("keyring entry key mismatch: {}", item.key));
+        }
+
+        Ok(item)
+    }
+
+    /// Encrypt and write an entry.
+    fn write_item(&self, item: &Item) -> Result<()> {
+        let plaintext = Zeroizing::new(serde_json::to_vec(item)?);
+        let jwe = jwe::encrypt(&plaintext, &self.passphrase)?;
+        let path = self.path(&item.key);
+
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+
+        #[cfg(unix)]
+        options.mode(0o600);
+
+        options
+            .open(&path)
+            .and_then(|mut file| file.write_all(jwe.as_bytes()))
+            .wrap_err_with(|| format!("error writing keyring entry: {}", path.display()))
+    }
+}
+
+impl fmt::Debug for FileKeyring {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileKeyring")
+            .field("dir", &self.dir)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Local secp256k1 key stored in a keyring, including its private key.
+#[derive(Clone)]
+pub struct LocalKey {
+    /// Name of the key.
+    name: String,
+
+    /// Raw secp256k1 private key.
+    private_key: Zeroizing<Vec<u8>>,
+
+    /// Public key.
+    public_key: PublicKey,
+}
+
+impl LocalKey {
+    /// Create a [`LocalKey`] from a raw secp256k1 private key (i.e. scalar).
+    pub fn new(name: impl Into<String>, private_key: &[u8]) -> Result<Self> {
+        let public_key = secp256k1::SigningKey::from_bytes(private_key)?.public_key();
+
+        Ok(Self {
+            name: name.into(),
+            private_key: Zeroizing::new(private_key.to_vec()),
+            public_key,
+        })
+    }
+
+    /// Generate a random [`LocalKey`].
+    pub fn random(name: impl Into<String>) -> Self {
+        let signing_key = k256::ecdsa::SigningKey::random(&mut rand_core::OsRng);
+        Self::new(name, &signing_key.to_bytes()).expect("invalid secp256k1 key")
+    }
+
+    /// Import a [`LocalKey`] from an ASCII-armored private key encrypted with
+    /// the given passphrase, e.g. one exported with `gaiad keys export`.
+    pub fn from_armor(name: impl Into<String>, armored: &str, passphrase: &str) -> Result<Self> {
+        Self::new(name, &armor::decrypt(armored, passphrase)?)
+    }
+
+    /// Export this key as an ASCII-armored private key encrypted with the
+    /// given passphrase, which can be imported with `gaiad keys import`.
+    pub fn to_armor(&self, passphrase: &str) -> Result<String> {
+        armor::encrypt(&self.private_key, passphrase)
+    }
+
+    /// Get the name of this key.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the [`PublicKey`] for this key.
+    pub fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    /// Get the [`AccountId`] for this key with the given prefix.
+    pub fn account_id(&self, prefix: &str) -> Result<AccountId> {
+        self.public_key.account_id(prefix)
+    }
+
+    /// Get a [`secp256k1::SigningKey`] for this key.
+    pub fn signing_key(&self) -> Result<secp256k1::SigningKey> {
+        secp256k1::SigningKey::from_bytes(&self.private_key)
+    }
+}
+
+impl fmt::Debug for LocalKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalKey")
+            .field("name", &self.name)
+            .field("public_key", &self.public_key)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Keyring entry, i.e. a JSON-encoded `keyring.Item` from `99designs/keyring`.
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct Item {
+    /// Key of the entry, e.g. `<name>.info`.
+    key: String,
+
+    /// Data stored in the entry.
+    #[serde(with = "crate::serializers::base64_bytes")]
+    data: Vec<u8>,
+
+    /// Label of the entry (unused).
+    #[serde(default)]
+    label: String,
+
+    /// Description of the entry (unused).
+    #[serde(default)]
+    description: String,
+
+    /// macOS keychain setting (unused).
+    #[serde(default)]
+    keychain_not_trust_application: bool,
+
+    /// macOS keychain setting (unused).
+    #[serde(default)]
+    keychain_not_synchronizable: bool,
+}
+
+impl Drop for Item {
+    fn drop(&mut self) {
+        self.data.zeroize();
+    }
+}
+
+impl Item {
+    /// Create a new [`Item`] with the given key and data.
+    fn new(key: String, data: &[u8]) -> Self {
+        Self {
+            key,
+            data: data.to_vec(),
+            label: String::new(),
+            description: String::new(),
+            keychain_not_trust_application: false,
+            keychain_not_synchronizable: false,
+        }
+    }
+}
+
+/// Get the key of the entry which indexes a key by address.
+fn address_key(address: &AccountId) -> String {
+    let address_hex = String::from_utf8(hex::encode(address.to_bytes())).expect("hex is UTF-8");
+    format!("{}{}", address_hex, ADDRESS_SUFFIX)
+}
+
+/// Escape a key for use as a filename, like Go's `url.PathEscape`.
+fn filename_escape(key: &str) -> String {
+    let mut escaped = String::with_capacity(key.len());
+
+    for byte in key.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' => escaped.push(byte as char),
+            b'-' | b'_' | b'.' | b'~' | b'$' | b'&' | b'+' | b':' | b'=' | b'@' => {
+                escaped.push(byte as char)
+            }
+            _ => escaped.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+
+    escaped
+}
+
+/// Unescape a filename escaped with [`filename_escape`].
+fn filename_unescape(filename: &str) -> String {
+    let bytes = filename.as_bytes();
+    let mut unescaped = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let decoded = match bytes.get(i..i + 3) {
+            Some([b'%', hi, lo]) => std::str::from_utf8(&[*hi, *lo])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+
+        match decoded {
+            Some(byte) => {
+                unescaped.push(byte);
+                i += 3;
+            }
+            None => {
+                unescaped.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&unescaped).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{filename_escape, filename_unescape, FileKeyring, LocalKey};
+    use crate::Error;
+    use std::fs;
+
+    /// `validator.info` entry in a keyring with the passphrase `12345678`,
+    /// encoded as an Amino `localInfo`.
+    ///
+    /// Like the entry below, this uses a fixed salt and nonce rather than
+    /// being written by `gaiad keys add --keyring-backend file`.
+    const VALIDATOR_INFO: &str = "eyJhbGciOiJQQkVTMi1IUzI1NitBMTI4S1ciLCJjcmVhdGVkIjoiMjAyMi0wNi0wMSAxMjowMDowMC4wMDAwMDAgKzAwMDAgVVRDIiwiZW5jIjoiQTI1NkdDTSIsInAyYyI6ODE5MiwicDJzIjoiQndjSEJ3Y0hCd2NIQndjSCJ9.RCuKnRVVYmbUp12eRSLZs5xJ02OgmtdKDiD3dvhADtZteVCxF2ChLQ.BQUFBQUFBQUFBQUF.tFX9M3L6Jm80RTmXuQ4dGnPi2_7TvJTgIDB31QvVmi3JF8zLNJZyDAI1jRL-HkpjujZJRVJJZZdqgVTopzx8-8jkVZZ6T_1nWQqCkPBv5WomYVHhN6ZW0rQ_mMfqa5nhCY2XcbfyPjHOaYl0EB-uSCCEwxHSZtTxBtb6ljavhsANr8joJV1WB4G2WsZa5i3nRdkpyR41slhtsUEmEwLPpoavs4zSLrDSCmI393rm-HaU8kzLxEfYAf0s4LvSPHGj4RrsZ1JRZiSFFjbHDjRe8mLzQ5xhkm3Cpe2Y30mPpsKCCLYMNKnDBcraDt_BYqTAkcGA192N_5WAI7SVI_pwMcCyd6ukM-vDx1OtloQt-sm5AGQT.oDFfx5XXkg9rZBYw60N2Pw";
+
+    /// Address index entry for `validator.info`.
+    const VALIDATOR_ADDRESS: &str = "eyJhbGciOiJQQkVTMi1IUzI1NitBMTI4S1ciLCJjcmVhdGVkIjoiMjAyMi0wNi0wMSAxMjowMDowMC4wMDAwMDAgKzAwMDAgVVRDIiwiZW5jIjoiQTI1NkdDTSIsInAyYyI6ODE5MiwicDJzIjoiQ0FnSUNBZ0lDQWdJQ0FnSSJ9.UxFCabVQStEh07zC-plfquwHmIFk-zfg7DKR07nxCzoCpfWgjMcxWA.BgYGBgYGBgYGBgYG.vPkMYttaZXLcFwRFqQg3Kmug-4eKL4sVm6Bstm6zVAJ8u43EOuKJ_QSIBaQpPJbkPiwgBSxjoO9Fc5X1H43BM5Yg7NxnPaFLjvXU6jMnfpYVbiTaHdz8HyCeo2JL0zZTlgHid-OZ6FvvgX5XW7r4tNBH-PourBZk-DpNOGWbeotYSPFFCB8jPHLLHEqveUK4DMT8o_f7qnmxgupapwpsJdXPfbR2ryiSFGXh-SMwXIoJxkbtSb7ZPxzR.p1pRGS7UGivXTRCszmiEpQ";
+
+    const VALIDATOR_ACCOUNT_ID: &str = "cosmos1u8xj76qe646058jwztjtutuczkjgv6q2x5s6vr";
+
+    #[test]
+    fn read_existing() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("validator.info"), VALIDATOR_INFO).unwrap();
+        fs::write(
+            dir.path()
+                .join("e1cd2f6819d574fa1e4e12e4be2f9815a486680a.address"),
+            VALIDATOR_ADDRESS,
+        )
+        .unwrap();
+
+        let keyring = FileKeyring::new(dir.path(), "12345678");
+        assert_eq!(keyring.list().unwrap(), ["validator"]);
+
+        let key = keyring.get("validator").unwrap();
+        assert_eq!(
+            key.public_key().to_bytes(),
+            subtle_encoding::base64::decode("AsFYQdVm0JGhiPNzkfrxXuVHrgcqWzIUTDR4eNV/LqQA")
+                .unwrap()
+        );
+        assert_eq!(
+            key.account_id("cosmos").unwrap().as_ref(),
+            VALIDATOR_ACCOUNT_ID
+        );
+
+        let by_address = keyring
+            .get_by_address(&VALIDATOR_ACCOUNT_ID.parse().unwrap())
+            .unwrap();
+        assert_eq!(by_address.public_key(), key.public_key());
+
+        let err = FileKeyring::new(dir.path(), "wrong")
+            .get("validator")
+            .unwrap_err();
+        assert_eq!(err.downcast_ref::<Error>(), Some(&Error::Crypto));
+    }
+
+    #[test]
+    fn add_and_delete() {
+        let dir = tempfile::tempdir().unwrap();
+        let keyring = FileKeyring::new(dir.path().join("keyring-file"), "passphrase");
+        let key = LocalKey::random("alice");
+
+        keyring.add(&key).unwrap();
+        assert!(matches!(
+            keyring.add(&key).unwrap_err().downcast_ref::<Error>(),
+            Some(Error::KeyExists { .. })
+        ));
+
+        let signing_key = keyring.signing_key("alice").unwrap();
+        assert_eq!(signing_key.public_key(), key.public_key());
+
+        keyring.delete("alice").unwrap();
+        assert!(keyring.list().unwrap().is_empty());
+        assert!(matches!(
+            keyring.get("alice").unwrap_err().downcast_ref::<Error>(),
+            Some(Error::KeyNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn filenames() {
+        assert_eq!(filename_escape("alice.info"), "alice.info");
+        assert_eq!(filename_escape("a/b c"), "a%2Fb%20c");
+        assert_eq!(filename_unescape("a%2Fb%20c"), "a/b c");
+
+        // Go's `url.PathEscape` escapes `,` and `;`, but not other sub-delims
+        assert_eq!(filename_escape("a,b;c"), "a%2Cb%3Bc");
+        assert_eq!(filename_escape("$&+:=@"), "$&+:=@");
+        assert_eq!(filename_unescape("a%2Cb%3Bc"), "a,b;c");
+    }
+}
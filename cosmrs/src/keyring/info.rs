@@ -0,0 +1,281 @@
+//! Serialization of keyring entries.
+//!
+//! Cosmos SDK v0.45 and earlier serialize entries as length-prefixed Amino
+//! `crypto/keys/localInfo` values, while v0.46 and later serialize them as
+//! `cosmos.crypto.keyring.v1.Record` Protobuf messages, and migrate Amino
+//! entries when they're read. Both are supported when reading, and entries
+//! are written using Amino so they can be read by all SDK versions.
+
+use super::LocalKey;
+use crate::{proto, Any, Error, Result};
+use eyre::WrapErr;
+use prost::Message;
+use zeroize::Zeroizing;
+
+/// Amino prefix for `crypto/keys/localInfo`.
+const LOCAL_INFO_PREFIX: [u8; 4] = [0x0d, 0xad, 0x15, 0x3d];
+
+/// Amino prefix for `tendermint/PubKeySecp256k1`.
+const PUB_KEY_SECP256K1_PREFIX: [u8; 4] = [0xeb, 0x5a, 0xe9, 0x87];
+
+/// Amino prefix for `tendermint/PrivKeySecp256k1`.
+const PRIV_KEY_SECP256K1_PREFIX: [u8; 4] = [0xe1, 0xb0, 0xf7, 0x9b];
+
+/// Key algorithm name for secp256k1 keys.
+const SECP256K1_ALGO: &str = "secp256k1";
+
+/// Protobuf [`Any`] type URL for secp256k1 private keys.
+const SECP256K1_PRIV_KEY_TYPE_URL: &str = "/cosmos.crypto.secp256k1.PrivKey";
+
+/// Protobuf `cosmos.crypto.keyring.v1.Record` (Cosmos SDK v0.46+).
+#[derive(Clone, PartialEq, Message)]
+struct Record {
+    /// Name of the key.
+    #[prost(string, tag = "1")]
+    name: String,
+
+    /// Public key.
+    #[prost(message, optional, tag = "2")]
+    pub_key: Option<Any>,
+
+    /// Local key, i.e. one whose private key is stored in the keyring.
+    #[prost(message, optional, tag = "3")]
+    local: Option<Local>,
+}
+
+/// Protobuf `cosmos.crypto.keyring.v1.Record.Local`.
+#[derive(Clone, PartialEq, Message)]
+struct Local {
+    /// Private key.
+    #[prost(message, optional, tag = "1")]
+    priv_key: Option<Any>,
+}
+
+/// Decode a [`LocalKey`] from a serialized keyring entry.
+pub(super) fn decode(bytes: &[u8]) -> Result<LocalKey> {
+    match Record::decode(bytes) {
+        Ok(Record {
+            name,
+            local: Some(Local {
+                priv_key: Some(priv_key),
+            }),
+            ..
+        }) if !name.is_empty() => decode_record(name, priv_key),
+        _ => decode_amino(bytes),
+    }
+}
+
+/// Encode a [`LocalKey`] as a length-prefixed Amino `crypto/keys/localInfo`.
+pub(super) fn encode(key: &LocalKey) -> Zeroizing<Vec<u8>> {
+    let mut pub_key = PUB_KEY_SECP256K1_PREFIX.to_vec();
+    encode_bytes(&key.public_key().to_bytes(), &mut pub_key);
+
+    let priv_key = encode_priv_key(&key.private_key);
+    let mut info = Zeroizing::new(LOCAL_INFO_PREFIX.to_vec());
+    encode_field(1, key.name().as_bytes(), &mut info);
+    encode_field(2, &pub_key, &mut info);
+    encode_field(3, &priv_key, &mut info);
+    encode_field(4, SECP256K1_ALGO.as_bytes(), &mut info);
+
+    let mut bytes = Zeroizing::new(Vec::with_capacity(info.len() + 2));
+    encode_bytes(&info, &mut bytes);
+    bytes
+}
+
+/// Decode a Protobuf `Record` containing a local key.
+fn decode_record(name: String, priv_key: Any) -> Result<LocalKey> {
+    if priv_key.type_url != SECP256K1_PRIV_KEY_TYPE_URL {
+        return Err(Error::Crypto)
+            .wrap_err_with(|| format!("unsupported private key type: {}", priv_key.type_url));
+    }
+
+    let priv_key =
+        Zeroizing::new(proto::cosmos::crypto::secp256k1::PrivKey::decode(&*priv_key.value)?.key);
+
+    LocalKey::new(name, &priv_key)
+}
+
+/// Decode a length-prefixed Amino `crypto/keys/localInfo`.
+fn decode_amino(bytes: &[u8]) -> Result<LocalKey> {
+    let mut reader = bytes;
+    let info = decode_bytes(&mut reader)?;
+
+    if !reader.is_empty() {
+        return Err(Error::Crypto).wrap_err("trailing data after keyring entry");
+    }
+
+    let mut fields = info
+        .strip_prefix(&LOCAL_INFO_PREFIX)
+        .ok_or(Error::Crypto)
+        .wrap_err("keyring entry isn't a local key")?;
+
+    let mut name = None;
+    let mut priv_key = None;
+    let mut algo = None;
+
+    while !fields.is_empty() {
+        let key = decode_uvarint(&mut fields)?;
+
+        if key & 0b111 != 2 {
+            return Err(Error::Crypto)
+                .wrap_err_with(|| format!("unexpected Amino wire type in field key: {}", key));
+        }
+
+        let value = decode_bytes(&mut fields)?;
+
+        match key >> 3 {
+            1 => name = Some(String::from_utf8(value.to_vec())?),
+            3 => priv_key = Some(value),
+            4 => algo = Some(value),
+            // The public key is derived from the private key
+            _ => (),
+        }
+    }
+
+    if let Some(algo) = algo {
+        if algo != SECP256K1_ALGO.as_bytes() {
+            return Err(Error::Crypto).wrap_err_with(|| {
+                format!(
+                    "unsupported key algorithm: {}",
+                    String::from_utf8_lossy(algo)
+                )
+            });
+        }
+    }
+
+    let priv_key = decode_priv_key(priv_key.ok_or(Error::MissingField {
+        name: "privkey.armor",
+    })?)?;
+
+    LocalKey::new(name.ok_or(Error::MissingField { name: "name" })?, priv_key)
+}
+
+/// Encode a raw secp256k1 private key as an Amino `tendermint/PrivKeySecp256k1`.
+pub(super) fn encode_priv_key(private_key: &[u8]) -> Zeroizing<Vec<u8>> {
+    let mut bytes = Zeroizing::new(PRIV_KEY_SECP256K1_PREFIX.to_vec());
+    encode_bytes(private_key, &mut bytes);
+    bytes
+}
+
+/// Decode a raw secp256k1 private key from an Amino `tendermint/PrivKeySecp256k1`.
+pub(super) fn decode_priv_key(bytes: &[u8]) -> Result<&[u8]> {
+    let mut reader = bytes
+        .strip_prefix(&PRIV_KEY_SECP256K1_PREFIX)
+        .ok_or(Error::Crypto)
+        .wrap_err("unsupported private key type")?;
+
+    let private_key = decode_bytes(&mut reader)?;
+
+    if !reader.is_empty() {
+        return Err(Error::Crypto).wrap_err("trailing data after private key");
+    }
+
+    Ok(private_key)
+}
+
+/// Encode a field of an Amino struct with the given field number.
+fn encode_field(number: u64, value: &[u8], out: &mut Vec<u8>) {
+    if !value.is_empty() {
+        encode_uvarint(number << 3 | 2, out);
+        encode_bytes(value, out);
+    }
+}
+
+/// Encode a length-prefixed byte slice.
+fn encode_bytes(bytes: &[u8], out: &mut Vec<u8>) {
+    encode_uvarint(bytes.len() as u64, out);
+    out.extend_from_slice(bytes);
+}
+
+/// Encode an unsigned varint.
+fn encode_uvarint(mut value: u64, out: &mut Vec<u8>) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+
+    out.push(value as u8);
+}
+
+/// Decode a length-prefixed byte slice.
+fn decode_bytes<'a>(reader: &mut &'a [u8]) -> Result<&'a [u8]> {
+    let len = usize::try_from(decode_uvarint(reader)?)?;
+
+    if len > reader.len() {
+        return Err(Error::Crypto).wrap_err("truncated keyring entry");
+    }
+
+    let (bytes, rest) = reader.split_at(len);
+    *reader = rest;
+    Ok(bytes)
+}
+
+/// Decode an unsigned varint.
+fn decode_uvarint(reader: &mut &[u8]) -> Result<u64> {
+    let mut value = 0u64;
+
+    for (i, byte) in reader.iter().enumerate().take(10) {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+
+        if byte & 0x80 == 0 {
+            *reader = &reader[(i + 1)..];
+            return Ok(value);
+        }
+    }
+
+    Err(Error::Crypto).wrap_err("malformed varint in keyring entry")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, encode, Local, Record, SECP256K1_PRIV_KEY_TYPE_URL};
+    use crate::{keyring::LocalKey, prost_ext::MessageExt, proto, Any};
+
+    #[test]
+    fn amino_round_trip() {
+        let key = LocalKey::random("alice");
+        let decoded = decode(&encode(&key)).unwrap();
+
+        assert_eq!(decoded.name(), "alice");
+        assert_eq!(decoded.public_key(), key.public_key());
+    }
+
+    /// Entries written by Cosmos SDK v0.46 and later are Protobuf `Record`s.
+    ///
+    /// This is constructed from the `Record` definition rather than being
+    /// written by `gaiad keys add --keyring-backend file`.
+    #[test]
+    fn decode_protobuf_record() {
+        let key = LocalKey::random("bob");
+        let priv_key = proto::cosmos::crypto::secp256k1::PrivKey {
+            key: key.private_key.to_vec(),
+        };
+
+        let record = Record {
+            name: "bob".to_owned(),
+            pub_key: Some(key.public_key().to_any().unwrap()),
+            local: Some(Local {
+                priv_key: Some(Any {
+                    type_url: SECP256K1_PRIV_KEY_TYPE_URL.to_owned(),
+                    value: priv_key.to_bytes().unwrap(),
+                }),
+            }),
+        };
+
+        let decoded = decode(&record.to_bytes().unwrap()).unwrap();
+        assert_eq!(decoded.name(), "bob");
+        assert_eq!(decoded.public_key(), key.public_key());
+
+        // Only secp256k1 private keys are supported
+        let mut record = record;
+        record
+            .local
+            .as_mut()
+            .unwrap()
+            .priv_key
+            .as_mut()
+            .unwrap()
+            .type_url = "/cosmos.crypto.ed25519.PrivKey".to_owned();
+        assert!(decode(&record.to_bytes().unwrap()).is_err());
+    }
+}
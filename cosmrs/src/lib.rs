@@ -6,6 +6,7 @@
 #![forbid(unsafe_code)]
 #![warn(trivial_casts, trivial_numeric_casts, unused_import_braces)]
 
+pub mod abci;
 pub mod bank;
 pub mod crypto;
 pub mod distribution;
@@ -19,10 +20,15 @@ pub mod cosmwasm;
 #[cfg_attr(docsrs, doc(cfg(feature = "dev")))]
 pub mod dev;
 
+#[cfg(feature = "keyring")]
+#[cfg_attr(docsrs, doc(cfg(feature = "keyring")))]
+pub mod keyring;
+
 mod base;
 mod decimal;
 mod error;
 mod prost_ext;
+mod serializers;
 
 pub use crate::{
     base::{AccountId, Coin, Denom},
@@ -0,0 +1,113 @@
+//! Serde helpers for the Protobuf JSON encoding of Cosmos SDK types.
+
+/// Serialize `u64` values as strings, which is how Protobuf JSON encodes
+/// 64-bit integers. Deserialization accepts either strings or numbers.
+pub(crate) mod u64_string {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+    use serde_json::Value;
+
+    pub fn serialize<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        match Value::deserialize(deserializer)? {
+            Value::String(s) => s.parse().map_err(de::Error::custom),
+            Value::Number(n) => n
+                .as_u64()
+                .ok_or_else(|| de::Error::custom(format!("invalid u64: {}", n))),
+            other => Err(de::Error::custom(format!("invalid u64: {}", other))),
+        }
+    }
+}
+
+/// Serialize byte vectors as Base64 strings.
+pub(crate) mod base64_bytes {
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+    use subtle_encoding::base64;
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        String::from_utf8(base64::encode(bytes))
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        base64::decode(String::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+/// Serialize vectors of byte vectors as arrays of Base64 strings.
+pub(crate) mod base64_vec {
+    use serde::{de, ser::SerializeSeq, Deserialize, Deserializer, Serializer};
+    use subtle_encoding::base64;
+
+    pub fn serialize<S: Serializer>(values: &[Vec<u8>], serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(values.len()))?;
+
+        for bytes in values {
+            let encoded =
+                String::from_utf8(base64::encode(bytes)).map_err(serde::ser::Error::custom)?;
+            seq.serialize_element(&encoded)?;
+        }
+
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Vec<u8>>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|s| base64::decode(s).map_err(de::Error::custom))
+            .collect()
+    }
+}
+
+/// Serialize optional values as strings, where [`None`] is represented as
+/// an empty string.
+pub(crate) mod optional_string {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+    use std::{fmt::Display, str::FromStr};
+
+    pub fn serialize<S, T>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Display,
+    {
+        match value {
+            Some(value) => serializer.collect_str(value),
+            None => serializer.serialize_str(""),
+        }
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: FromStr,
+        T::Err: Display,
+    {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(s) if !s.is_empty() => s.parse().map(Some).map_err(de::Error::custom),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Serialize bytes containing JSON as the raw JSON value, which is how
+/// CosmWasm's `RawContractMessage` is encoded.
+#[cfg(feature = "cosmwasm")]
+pub(crate) mod raw_json {
+    use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+    use serde_json::Value;
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serde_json::from_slice::<Value>(bytes)
+            .map_err(ser::Error::custom)?
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        serde_json::to_vec(&Value::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
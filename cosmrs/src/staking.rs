@@ -2,10 +2,19 @@
 //!
 //! <https://docs.cosmos.network/master/modules/staking/>
 
-use crate::{proto, tx::Msg, AccountId, Coin, Error, ErrorReport, Result};
+use crate::{
+    base::validate_coins,
+    proto,
+    tx::{LegacyAminoMsg, Msg},
+    AccountId, Coin, Error, ErrorReport, Result,
+};
+use eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::slice;
 
 /// MsgDelegate represents a message to delegate coins to a validator.
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, PartialOrd, Ord, Serialize)]
 pub struct MsgDelegate {
     /// Delegator's address.
     pub delegator_address: AccountId,
@@ -19,6 +28,26 @@ pub struct MsgDelegate {
 
 impl Msg for MsgDelegate {
     type Proto = proto::cosmos::staking::v1beta1::MsgDelegate;
+
+    fn signers(&self) -> Result<Vec<AccountId>> {
+        Ok(vec![self.delegator_address.clone()])
+    }
+
+    fn validate_basic(&self) -> Result<()> {
+        validate_coins(slice::from_ref(&self.amount)).wrap_err("invalid delegation amount")
+    }
+}
+
+impl LegacyAminoMsg for MsgDelegate {
+    const AMINO_TYPE: &'static str = "cosmos-sdk/MsgDelegate";
+
+    fn to_amino_value(&self) -> Result<serde_json::Value> {
+        Ok(json!({
+            "delegator_address": self.delegator_address,
+            "validator_address": self.validator_address,
+            "amount": self.amount,
+        }))
+    }
 }
 
 impl TryFrom<proto::cosmos::staking::v1beta1::MsgDelegate> for MsgDelegate {
@@ -71,7 +100,7 @@ impl From<&MsgDelegate> for proto::cosmos::staking::v1beta1::MsgDelegate {
 }
 
 /// MsgUndelegate represents a message to undelegate coins from a validator.
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, PartialOrd, Ord, Serialize)]
 pub struct MsgUndelegate {
     /// Delegator's address.
     pub delegator_address: AccountId,
@@ -85,6 +114,26 @@ pub struct MsgUndelegate {
 
 impl Msg for MsgUndelegate {
     type Proto = proto::cosmos::staking::v1beta1::MsgUndelegate;
+
+    fn signers(&self) -> Result<Vec<AccountId>> {
+        Ok(vec![self.delegator_address.clone()])
+    }
+
+    fn validate_basic(&self) -> Result<()> {
+        validate_coins(slice::from_ref(&self.amount)).wrap_err("invalid shares amount")
+    }
+}
+
+impl LegacyAminoMsg for MsgUndelegate {
+    const AMINO_TYPE: &'static str = "cosmos-sdk/MsgUndelegate";
+
+    fn to_amino_value(&self) -> Result<serde_json::Value> {
+        Ok(json!({
+            "delegator_address": self.delegator_address,
+            "validator_address": self.validator_address,
+            "amount": self.amount,
+        }))
+    }
 }
 
 impl TryFrom<proto::cosmos::staking::v1beta1::MsgUndelegate> for MsgUndelegate {
@@ -137,7 +186,7 @@ impl From<&MsgUndelegate> for proto::cosmos::staking::v1beta1::MsgUndelegate {
 }
 
 /// MsgBeginRedelegate represents a message to redelegate coins from one validator to another.
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, PartialOrd, Ord, Serialize)]
 pub struct MsgBeginRedelegate {
     /// Delegator's address.
     pub delegator_address: AccountId,
@@ -154,6 +203,27 @@ pub struct MsgBeginRedelegate {
 
 impl Msg for MsgBeginRedelegate {
     type Proto = proto::cosmos::staking::v1beta1::MsgBeginRedelegate;
+
+    fn signers(&self) -> Result<Vec<AccountId>> {
+        Ok(vec![self.delegator_address.clone()])
+    }
+
+    fn validate_basic(&self) -> Result<()> {
+        validate_coins(slice::from_ref(&self.amount)).wrap_err("invalid shares amount")
+    }
+}
+
+impl LegacyAminoMsg for MsgBeginRedelegate {
+    const AMINO_TYPE: &'static str = "cosmos-sdk/MsgBeginRedelegate";
+
+    fn to_amino_value(&self) -> Result<serde_json::Value> {
+        Ok(json!({
+            "delegator_address": self.delegator_address,
+            "validator_src_address": self.validator_src_address,
+            "validator_dst_address": self.validator_dst_address,
+            "amount": self.amount,
+        }))
+    }
 }
 
 impl TryFrom<proto::cosmos::staking::v1beta1::MsgBeginRedelegate> for MsgBeginRedelegate {
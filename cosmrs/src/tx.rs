@@ -104,28 +104,47 @@
 
 pub mod mode_info;
 
+mod any_msg;
 mod auth_info;
 mod body;
+mod builder;
 mod fee;
+mod gas_info;
+mod gas_price;
+mod json;
 mod msg;
+mod multisig;
+mod partially_signed;
 mod raw;
 mod sign_doc;
 mod signer_info;
+mod std_sign_doc;
 
 pub use self::{
+    any_msg::{AnyMsg, MsgRegistry},
     auth_info::AuthInfo,
     body::Body,
+    builder::{Builder, SignerData},
     fee::Fee,
+    gas_info::GasInfo,
+    gas_price::{GasPrice, GasPrices},
     mode_info::ModeInfo,
-    msg::{Msg, MsgProto},
+    msg::{LegacyAminoMsg, Msg, MsgProto},
+    multisig::MultisigSignature,
+    partially_signed::PartiallySigned,
     raw::Raw,
     sign_doc::SignDoc,
     signer_info::{SignerInfo, SignerPublicKey},
+    std_sign_doc::StdSignDoc,
 };
 pub use crate::{proto::cosmos::tx::signing::v1beta1::SignMode, ErrorReport};
 pub use tendermint::abci::{transaction::Hash, Gas};
 
+#[cfg(feature = "grpc")]
+pub use self::raw::ServiceClient;
+
 use crate::{proto, Error, Result};
+use eyre::WrapErr;
 use prost::Message;
 
 #[cfg(feature = "rpc")]
@@ -140,6 +159,13 @@ pub type SequenceNumber = u64;
 /// Serialized signature.
 pub type SignatureBytes = Vec<u8>;
 
+/// Default maximum length of a transaction memo in bytes, i.e. the default
+/// value of the `auth` module's `max_memo_characters` parameter.
+pub const DEFAULT_MAX_MEMO_CHARACTERS: usize = 256;
+
+/// Maximum gas limit of a transaction.
+pub const MAX_GAS_WANTED: u64 = i64::MAX as u64;
+
 /// [`Tx`] is the standard type used for broadcasting transactions.
 #[derive(Clone, Debug)]
 pub struct Tx {
@@ -164,6 +190,141 @@ impl Tx {
         Tx::try_from(bytes)
     }
 
+    /// Parse a [`Tx`] from its Protobuf JSON encoding, e.g. the output of
+    /// `gaiad tx sign`.
+    ///
+    /// Messages are decoded according to their `@type`, and an error is
+    /// returned for message types which are not supported by this crate.
+    pub fn from_json(s: &str) -> Result<Tx> {
+        json::decode(serde_json::from_str(s)?)
+    }
+
+    /// Serialize this [`Tx`] using the Protobuf JSON encoding.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(&json::encode(self)?)?)
+    }
+
+    /// Serialize this [`Tx`] as a [`Raw`] transaction.
+    pub fn into_raw(self) -> Result<Raw> {
+        Ok(proto::cosmos::tx::v1beta1::TxRaw {
+            body_bytes: self.body.into_bytes()?,
+            auth_info_bytes: self.auth_info.into_bytes()?,
+            signatures: self.signatures,
+        }
+        .into())
+    }
+
+    /// Compute the transaction hash.
+    ///
+    /// See [`Raw::hash`] for more information. Note that this method re-encodes
+    /// the transaction's body and auth info, so the hash will only match the
+    /// hash of the original serialization if it was canonically encoded.
+    pub fn hash(&self) -> Result<Hash> {
+        self.clone().into_raw()?.hash()
+    }
+
+    /// Verify the signatures of this transaction.
+    ///
+    /// See [`Raw::verify_signatures`] for more information. Note that this method
+    /// re-encodes the transaction's body and auth info, so verifying the [`Raw`]
+    /// transaction is preferable when the original serialization is available.
+    pub fn verify_signatures(
+        &self,
+        chain_id: &tendermint::chain::Id,
+        account_numbers: &[AccountNumber],
+    ) -> Result<()> {
+        self.clone()
+            .into_raw()?
+            .verify_signatures(chain_id, account_numbers)
+    }
+
+    /// Perform the stateless checks that a node performs before accepting
+    /// this transaction into its mempool, i.e. `ValidateBasic` and the
+    /// stateless ante handler checks of the Cosmos SDK.
+    ///
+    /// Each message is validated with [`AnyMsg::validate_basic`].
+    ///
+    /// The memo length is checked against [`DEFAULT_MAX_MEMO_CHARACTERS`].
+    /// The number of signers is checked against the signers required by the
+    /// transaction's messages (see [`Body::required_signers`]) and fee payer
+    /// only if all messages are of types supported by this crate.
+    ///
+    /// Errors are returned as an [`ErrorReport`] wrapping an [`Error`] which
+    /// identifies the problem, e.g. [`Error::NoMessages`].
+    pub fn validate_basic(&self) -> Result<()> {
+        if self.body.messages.is_empty() {
+            return Err(Error::NoMessages.into());
+        }
+
+        if self.body.memo.len() > DEFAULT_MAX_MEMO_CHARACTERS {
+            return Err(Error::Memo {
+                len: self.body.memo.len(),
+                max: DEFAULT_MAX_MEMO_CHARACTERS,
+            }
+            .into());
+        }
+
+        let fee = &self.auth_info.fee;
+        let gas_limit = fee.gas_limit.value();
+
+        if gas_limit == 0 || gas_limit > MAX_GAS_WANTED {
+            return Err(Error::Gas { gas_limit }.into());
+        }
+
+        crate::base::validate_coins(&fee.amount).wrap_err("invalid fee")?;
+
+        if self.signatures.is_empty() {
+            return Err(Error::NoSignatures.into());
+        }
+
+        let signer_infos = &self.auth_info.signer_infos;
+
+        if self.signatures.len() != signer_infos.len() {
+            return Err(Error::SignerCount {
+                expected: signer_infos.len(),
+                found: self.signatures.len(),
+            })
+            .wrap_err("number of signatures doesn't match number of signer infos");
+        }
+
+        for (index, signer_info) in signer_infos.iter().enumerate() {
+            if signer_info.public_key.is_some()
+                && signer_infos[..index]
+                    .iter()
+                    .any(|other| other.public_key == signer_info.public_key)
+            {
+                return Err(Error::DuplicateSigner { index }.into());
+            }
+        }
+
+        let msgs = self.body.msgs()?;
+
+        for (index, msg) in msgs.iter().enumerate() {
+            msg.validate_basic()
+                .wrap_err_with(|| format!("invalid message {}", index))?;
+        }
+
+        if msgs.iter().all(|msg| !matches!(msg, AnyMsg::Unknown(_))) {
+            let mut signers = self.body.required_signers()?;
+
+            if let Some(payer) = &fee.payer {
+                if !signers.contains(payer) {
+                    signers.push(payer.clone());
+                }
+            }
+
+            if signers.len() != signer_infos.len() {
+                return Err(Error::SignerCount {
+                    expected: signers.len(),
+                    found: signer_infos.len(),
+                })
+                .wrap_err("number of signer infos doesn't match number of required signers");
+            }
+        }
+
+        Ok(())
+    }
+
     /// Use RPC to find a transaction by its hash.
     #[cfg(feature = "rpc")]
     #[cfg_attr(docsrs, doc(cfg(feature = "rpc")))]
@@ -211,3 +372,145 @@ impl From<Tx> for proto::cosmos::tx::v1beta1::Tx {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{AuthInfo, Body, Fee, Msg, SignerInfo, Tx};
+    use crate::{bank::MsgSend, crypto::secp256k1, staking::MsgDelegate, Coin, Error};
+
+    fn coin(amount: u64, denom: &str) -> Coin {
+        Coin {
+            denom: denom.parse().unwrap(),
+            amount: amount.into(),
+        }
+    }
+
+    fn example_tx() -> (Tx, secp256k1::SigningKey) {
+        let sender = secp256k1::SigningKey::random();
+
+        let msg_send = MsgSend {
+            from_address: sender.public_key().account_id("cosmos").unwrap(),
+            to_address: "cosmos19dyl0uyzes4k23lscla02n06fc22h4uqsdwq6z"
+                .parse()
+                .unwrap(),
+            amount: vec![coin(1_000_000, "uatom")],
+        };
+
+        let tx = Tx {
+            body: Body::new(vec![msg_send.to_any().unwrap()], "memo", 0u16),
+            auth_info: AuthInfo {
+                signer_infos: vec![SignerInfo::single_direct(Some(sender.public_key()), 0)],
+                fee: Fee::from_amount_and_gas(coin(5_000, "uatom"), 200_000u64),
+            },
+            signatures: vec![vec![0; 64]],
+        };
+
+        (tx, sender)
+    }
+
+    fn validation_error(tx: &Tx) -> Error {
+        tx.validate_basic()
+            .unwrap_err()
+            .downcast_ref::<Error>()
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn validate_basic_msgs() {
+        let (mut tx, sender) = example_tx();
+        let sender = sender.public_key().account_id("cosmos").unwrap();
+        let validator = "cosmosvaloper1qypqxpqpqgpsgqgzqvzqzqsrqsqsyqcyp767eh"
+            .parse()
+            .unwrap();
+
+        let empty_send = MsgSend {
+            from_address: sender.clone(),
+            to_address: sender.clone(),
+            amount: vec![],
+        };
+        assert!(empty_send.validate_basic().is_err());
+
+        tx.body.messages.push(empty_send.to_any().unwrap());
+        assert!(matches!(validation_error(&tx), Error::Coins { .. }));
+
+        let mut delegate = MsgDelegate {
+            delegator_address: sender,
+            validator_address: validator,
+            amount: coin(1, "uatom"),
+        };
+        delegate.validate_basic().unwrap();
+
+        delegate.amount = coin(0, "uatom");
+        assert!(delegate.validate_basic().is_err());
+    }
+
+    #[test]
+    fn validate_basic() {
+        let (tx, sender) = example_tx();
+        tx.validate_basic().unwrap();
+
+        let mut invalid = tx.clone();
+        invalid.body.messages.clear();
+        assert_eq!(validation_error(&invalid), Error::NoMessages);
+
+        let mut invalid = tx.clone();
+        invalid.body.memo = "x".repeat(257);
+        assert_eq!(
+            validation_error(&invalid),
+            Error::Memo { len: 257, max: 256 }
+        );
+
+        let mut invalid = tx.clone();
+        invalid.auth_info.fee.gas_limit = 0u64.into();
+        assert_eq!(validation_error(&invalid), Error::Gas { gas_limit: 0 });
+
+        let mut invalid = tx.clone();
+        invalid.auth_info.fee.amount = vec![coin(5_000, "uatom"), coin(1, "stake")];
+        assert!(matches!(validation_error(&invalid), Error::Coins { .. }));
+
+        let mut invalid = tx.clone();
+        invalid.auth_info.fee.amount = vec![coin(0, "uatom")];
+        assert!(matches!(validation_error(&invalid), Error::Coins { .. }));
+
+        let mut invalid = tx.clone();
+        invalid.signatures.clear();
+        assert_eq!(validation_error(&invalid), Error::NoSignatures);
+
+        let mut invalid = tx.clone();
+        invalid.signatures.push(vec![0; 64]);
+        assert_eq!(
+            validation_error(&invalid),
+            Error::SignerCount {
+                expected: 1,
+                found: 2
+            }
+        );
+
+        let mut invalid = tx.clone();
+        invalid.signatures.push(vec![0; 64]);
+        invalid
+            .auth_info
+            .signer_infos
+            .push(SignerInfo::single_direct(Some(sender.public_key()), 0));
+        assert_eq!(
+            validation_error(&invalid),
+            Error::DuplicateSigner { index: 1 }
+        );
+
+        // A fee payer is an additional required signer
+        let mut invalid = tx;
+        invalid.auth_info.fee.payer = Some(
+            "cosmos19dyl0uyzes4k23lscla02n06fc22h4uqsdwq6z"
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(
+            validation_error(&invalid),
+            Error::SignerCount {
+                expected: 2,
+                found: 1
+            }
+        );
+    }
+}
@@ -0,0 +1,420 @@
+//! Decoding of [`Any`] messages into their domain types.
+
+use super::{LegacyAminoMsg, Msg, MsgProto};
+use crate::{bank, distribution, staking, AccountId, Any, Error, ErrorReport, Result};
+use serde_json::{json, Value};
+use std::{collections::BTreeMap, fmt};
+
+#[cfg(feature = "cosmwasm")]
+use crate::cosmwasm;
+
+/// Defines [`AnyMsg`] along with its conversions from the given list of
+/// variants and [`Msg`] types.
+macro_rules! any_msg {
+    ($($(#[$attr:meta])* $variant:ident($msg:ty),)+) => {
+        /// Message of any of the types supported by this crate, decoded from [`Any`].
+        ///
+        /// Messages of other types are represented as [`AnyMsg::Unknown`]. To decode
+        /// messages of types defined outside of this crate, use a [`MsgRegistry`].
+        #[derive(Clone, Debug, PartialEq)]
+        #[non_exhaustive]
+        pub enum AnyMsg {
+            $(
+                #[doc = concat!("[`", stringify!($msg), "`] message.")]
+                $(#[$attr])*
+                $variant($msg),
+            )+
+
+            /// Message of a type which is not supported by this crate.
+            Unknown(Any),
+        }
+
+        impl AnyMsg {
+            /// Decode an [`AnyMsg`] from [`Any`].
+            ///
+            /// Returns [`AnyMsg::Unknown`] if the type URL isn't known, and an
+            /// error if the type URL is known but the message fails to decode.
+            pub fn from_any(any: &Any) -> Result<Self> {
+                match any.type_url.as_str() {
+                    $(
+                        $(#[$attr])*
+                        type_url if type_url == <$msg as Msg>::Proto::TYPE_URL => {
+                            <$msg>::from_any(any).map(Self::$variant)
+                        }
+                    )+
+                    _ => Ok(Self::Unknown(any.clone())),
+                }
+            }
+
+            /// Serialize this message as [`Any`].
+            pub fn to_any(&self) -> Result<Any> {
+                match self {
+                    $(
+                        $(#[$attr])*
+                        Self::$variant(msg) => msg.to_any(),
+                    )+
+                    Self::Unknown(any) => Ok(any.clone()),
+                }
+            }
+
+            /// Get the type URL of this message.
+            pub fn type_url(&self) -> &str {
+                match self {
+                    $(
+                        $(#[$attr])*
+                        Self::$variant(_) => <$msg as Msg>::Proto::TYPE_URL,
+                    )+
+                    Self::Unknown(any) => &any.type_url,
+                }
+            }
+
+            /// Get the addresses of the accounts which must sign this message.
+            ///
+            /// Returns an error for [`AnyMsg::Unknown`] messages, as their signers
+            /// can't be determined.
+            pub fn signers(&self) -> Result<Vec<AccountId>> {
+                match self {
+                    $(
+                        $(#[$attr])*
+                        Self::$variant(msg) => msg.signers(),
+                    )+
+                    Self::Unknown(any) => Err(unsupported(&any.type_url)),
+                }
+            }
+
+            /// Perform stateless validation of this message (see
+            /// [`Msg::validate_basic`]).
+            ///
+            /// [`AnyMsg::Unknown`] messages can't be validated and always pass.
+            pub fn validate_basic(&self) -> Result<()> {
+                match self {
+                    $(
+                        $(#[$attr])*
+                        Self::$variant(msg) => msg.validate_basic(),
+                    )+
+                    Self::Unknown(_) => Ok(()),
+                }
+            }
+
+            /// Serialize this message as Amino JSON.
+            pub(crate) fn to_amino_json(&self) -> Result<Value> {
+                match self {
+                    $(
+                        $(#[$attr])*
+                        Self::$variant(msg) => msg.to_amino_json(),
+                    )+
+                    Self::Unknown(any) => Err(unsupported(&any.type_url)),
+                }
+            }
+
+            /// Serialize this message as Protobuf JSON, including its `@type`.
+            pub(crate) fn to_json(&self) -> Result<Value> {
+                let mut value = match self {
+                    $(
+                        $(#[$attr])*
+                        Self::$variant(msg) => serde_json::to_value(msg)?,
+                    )+
+                    Self::Unknown(any) => return Err(unsupported(&any.type_url)),
+                };
+
+                value["@type"] = json!(self.type_url());
+                Ok(value)
+            }
+
+            /// Deserialize a message of the given type URL from Protobuf JSON.
+            pub(crate) fn from_json(type_url: &str, value: Value) -> Result<Self> {
+                match type_url {
+                    $(
+                        $(#[$attr])*
+                        type_url if type_url == <$msg as Msg>::Proto::TYPE_URL => {
+                            Ok(Self::$variant(serde_json::from_value(value)?))
+                        }
+                    )+
+                    _ => Err(unsupported(type_url)),
+                }
+            }
+
+            /// Register the decoders for all message types supported by this
+            /// crate with the given registry.
+            fn register_all<T: From<AnyMsg>>(registry: &mut MsgRegistry<T>) {
+                $(
+                    $(#[$attr])*
+                    registry.insert(<$msg as Msg>::Proto::TYPE_URL, decode_builtin::<$msg, T>);
+                )+
+            }
+        }
+
+        $(
+            $(#[$attr])*
+            impl From<$msg> for AnyMsg {
+                fn from(msg: $msg) -> AnyMsg {
+                    AnyMsg::$variant(msg)
+                }
+            }
+        )+
+    };
+}
+
+any_msg! {
+    MsgSend(bank::MsgSend),
+    MsgSetWithdrawAddress(distribution::MsgSetWithdrawAddress),
+    MsgWithdrawDelegatorReward(distribution::MsgWithdrawDelegatorReward),
+    MsgWithdrawValidatorCommission(distribution::MsgWithdrawValidatorCommission),
+    MsgFundCommunityPool(distribution::MsgFundCommunityPool),
+    MsgDelegate(staking::MsgDelegate),
+    MsgUndelegate(staking::MsgUndelegate),
+    MsgBeginRedelegate(staking::MsgBeginRedelegate),
+    #[cfg(feature = "cosmwasm")]
+    MsgStoreCode(cosmwasm::MsgStoreCode),
+    #[cfg(feature = "cosmwasm")]
+    MsgInstantiateContract(cosmwasm::MsgInstantiateContract),
+    #[cfg(feature = "cosmwasm")]
+    MsgExecuteContract(cosmwasm::MsgExecuteContract),
+    #[cfg(feature = "cosmwasm")]
+    MsgMigrateContract(cosmwasm::MsgMigrateContract),
+    #[cfg(feature = "cosmwasm")]
+    MsgUpdateAdmin(cosmwasm::MsgUpdateAdmin),
+    #[cfg(feature = "cosmwasm")]
+    MsgClearAdmin(cosmwasm::MsgClearAdmin),
+}
+
+impl TryFrom<&Any> for AnyMsg {
+    type Error = ErrorReport;
+
+    fn try_from(any: &Any) -> Result<AnyMsg> {
+        AnyMsg::from_any(any)
+    }
+}
+
+impl TryFrom<Any> for AnyMsg {
+    type Error = ErrorReport;
+
+    fn try_from(any: Any) -> Result<AnyMsg> {
+        AnyMsg::from_any(&any)
+    }
+}
+
+/// Function which decodes a message from [`Any`].
+type Decoder<T> = fn(&Any) -> Result<T>;
+
+/// Registry of [`Msg`] types which decodes [`Any`] messages into a type `T`.
+///
+/// This allows crates which define their own [`Msg`] types to decode them
+/// alongside the ones supported by this crate. `T` is typically an enum with
+/// a variant for [`AnyMsg`] and a variant for each additional message type:
+///
+/// ```
+/// use cosmrs::{
+///     tx::{AnyMsg, MsgRegistry},
+///     Any,
+/// };
+///
+/// #[derive(Debug)]
+/// enum MyMsg {
+///     Cosmos(AnyMsg),
+///     // Custom(my_chain::MsgCustom),
+/// }
+///
+/// impl From<AnyMsg> for MyMsg {
+///     fn from(msg: AnyMsg) -> MyMsg {
+///         MyMsg::Cosmos(msg)
+///     }
+/// }
+///
+/// let registry = MsgRegistry::<MyMsg>::new();
+/// // registry.register::<my_chain::MsgCustom>();
+///
+/// let any = Any {
+///     type_url: "/my_chain.v1.MsgCustom".to_owned(),
+///     value: vec![],
+/// };
+///
+/// assert!(matches!(
+///     registry.decode(&any).unwrap(),
+///     MyMsg::Cosmos(AnyMsg::Unknown(_))
+/// ));
+/// ```
+pub struct MsgRegistry<T = AnyMsg> {
+    /// Decoders indexed by type URL.
+    decoders: BTreeMap<String, Decoder<T>>,
+}
+
+impl<T: From<AnyMsg>> MsgRegistry<T> {
+    /// Create a new [`MsgRegistry`] containing all message types supported by
+    /// this crate (see [`AnyMsg`]).
+    pub fn new() -> Self {
+        let mut registry = Self {
+            decoders: BTreeMap::new(),
+        };
+
+        AnyMsg::register_all(&mut registry);
+        registry
+    }
+
+    /// Register the [`Msg`] type `M`, replacing any existing registration for
+    /// its type URL.
+    pub fn register<M>(&mut self) -> &mut Self
+    where
+        M: Msg,
+        T: From<M>,
+    {
+        self.insert(M::Proto::TYPE_URL, decode::<M, T>);
+        self
+    }
+
+    /// Is a message type with the given type URL registered?
+    pub fn contains(&self, type_url: &str) -> bool {
+        self.decoders.contains_key(type_url)
+    }
+
+    /// Decode a message from [`Any`].
+    ///
+    /// Messages whose type isn't registered are decoded as [`AnyMsg::Unknown`].
+    pub fn decode(&self, any: &Any) -> Result<T> {
+        match self.decoders.get(&any.type_url) {
+            Some(decoder) => decoder(any),
+            None => Ok(AnyMsg::Unknown(any.clone()).into()),
+        }
+    }
+
+    /// Insert a decoder for the given type URL.
+    fn insert(&mut self, type_url: &str, decoder: Decoder<T>) {
+        self.decoders.insert(type_url.to_owned(), decoder);
+    }
+}
+
+impl<T: From<AnyMsg>> Default for MsgRegistry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for MsgRegistry<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.decoders.keys()).finish()
+    }
+}
+
+/// Decode a message of type `M` from [`Any`] and convert it into `T`.
+fn decode<M, T>(any: &Any) -> Result<T>
+where
+    M: Msg,
+    T: From<M>,
+{
+    M::from_any(any).map(T::from)
+}
+
+/// Decode a message of type `M` from [`Any`] and convert it into `T` via
+/// [`AnyMsg`].
+fn decode_builtin<M, T>(any: &Any) -> Result<T>
+where
+    M: Msg + Into<AnyMsg>,
+    T: From<AnyMsg>,
+{
+    M::from_any(any).map(|msg| T::from(msg.into()))
+}
+
+/// Error for message types which don't have a known encoding.
+fn unsupported(type_url: &str) -> ErrorReport {
+    Error::UnsupportedMsg {
+        type_url: type_url.to_owned(),
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AnyMsg, MsgRegistry};
+    use crate::{bank::MsgSend, staking::MsgDelegate, tx::Msg, Any, Coin};
+
+    fn msg_send() -> MsgSend {
+        MsgSend {
+            from_address: "cosmos1qyqszqgpqyqszqgpqyqszqgpqyqszqgpjnp7du"
+                .parse()
+                .unwrap(),
+            to_address: "cosmos19dyl0uyzes4k23lscla02n06fc22h4uqsdwq6z"
+                .parse()
+                .unwrap(),
+            amount: vec![Coin {
+                denom: "uatom".parse().unwrap(),
+                amount: 1_000_000u64.into(),
+            }],
+        }
+    }
+
+    #[test]
+    fn decode_any() {
+        let any = msg_send().to_any().unwrap();
+        let msg = AnyMsg::from_any(&any).unwrap();
+
+        assert_eq!(msg, AnyMsg::MsgSend(msg_send()));
+        assert_eq!(msg.type_url(), "/cosmos.bank.v1beta1.MsgSend");
+        assert_eq!(msg.to_any().unwrap(), any);
+
+        let unknown = Any {
+            type_url: "/example.v1.MsgFoo".to_owned(),
+            value: vec![1, 2, 3],
+        };
+        assert_eq!(
+            AnyMsg::from_any(&unknown).unwrap(),
+            AnyMsg::Unknown(unknown.clone())
+        );
+
+        // Known type URL with an invalid encoding
+        let invalid = Any {
+            type_url: any.type_url,
+            value: vec![0xff],
+        };
+        assert!(AnyMsg::from_any(&invalid).is_err());
+    }
+
+    #[test]
+    fn registry() {
+        /// Example of a downstream message type, which isn't part of [`AnyMsg`]
+        /// in this test.
+        #[derive(Debug, PartialEq)]
+        enum TestMsg {
+            Builtin(AnyMsg),
+            Send(MsgSend),
+        }
+
+        impl From<AnyMsg> for TestMsg {
+            fn from(msg: AnyMsg) -> TestMsg {
+                TestMsg::Builtin(msg)
+            }
+        }
+
+        impl From<MsgSend> for TestMsg {
+            fn from(msg: MsgSend) -> TestMsg {
+                TestMsg::Send(msg)
+            }
+        }
+
+        let mut registry = MsgRegistry::<TestMsg>::new();
+        assert!(registry.contains("/cosmos.staking.v1beta1.MsgDelegate"));
+
+        let any = msg_send().to_any().unwrap();
+        assert_eq!(
+            registry.decode(&any).unwrap(),
+            TestMsg::Builtin(AnyMsg::MsgSend(msg_send()))
+        );
+
+        registry.register::<MsgSend>();
+        assert_eq!(registry.decode(&any).unwrap(), TestMsg::Send(msg_send()));
+
+        let delegate = MsgDelegate {
+            delegator_address: msg_send().from_address,
+            validator_address: "cosmosvaloper1qypqxpqpqgpsgqgzqvzqzqsrqsqsyqcyp767eh"
+                .parse()
+                .unwrap(),
+            amount: Coin {
+                denom: "uatom".parse().unwrap(),
+                amount: 5u64.into(),
+            },
+        };
+        assert!(matches!(
+            registry.decode(&delegate.to_any().unwrap()).unwrap(),
+            TestMsg::Builtin(AnyMsg::MsgDelegate(_))
+        ));
+    }
+}
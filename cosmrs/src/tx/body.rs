@@ -1,6 +1,7 @@
 //! Transaction bodies.
 
-use crate::{prost_ext::MessageExt, proto, ErrorReport, Result};
+use super::AnyMsg;
+use crate::{prost_ext::MessageExt, proto, AccountId, ErrorReport, Result};
 use prost_types::Any;
 use tendermint::block;
 
@@ -56,6 +57,37 @@ impl Body {
         }
     }
 
+    /// Decode the messages in this [`Body`] into their domain types.
+    ///
+    /// Messages of types which aren't supported by this crate are returned as
+    /// [`AnyMsg::Unknown`]. Use a [`MsgRegistry`][`super::MsgRegistry`] to
+    /// decode additional message types.
+    pub fn msgs(&self) -> Result<Vec<AnyMsg>> {
+        self.messages.iter().map(AnyMsg::from_any).collect()
+    }
+
+    /// Get the addresses of the accounts which must sign this transaction.
+    ///
+    /// These are the signers of each message in order, with each address
+    /// only included the first time it occurs. They determine the number and
+    /// order of the signer infos in the transaction's [`AuthInfo`][`super::AuthInfo`].
+    ///
+    /// Returns an error if any message is of a type which isn't supported by
+    /// this crate.
+    pub fn required_signers(&self) -> Result<Vec<AccountId>> {
+        let mut signers = Vec::<AccountId>::new();
+
+        for msg in self.msgs()? {
+            for signer in msg.signers()? {
+                if !signers.contains(&signer) {
+                    signers.push(signer);
+                }
+            }
+        }
+
+        Ok(signers)
+    }
+
     /// Convert the body to a Protocol Buffers representation.
     pub fn into_proto(self) -> proto::cosmos::tx::v1beta1::TxBody {
         self.into()
@@ -92,3 +124,58 @@ impl TryFrom<proto::cosmos::tx::v1beta1::TxBody> for Body {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Body;
+    use crate::{
+        bank::MsgSend,
+        distribution::{MsgWithdrawDelegatorReward, MsgWithdrawValidatorCommission},
+        tx::Msg,
+        AccountId, Any,
+    };
+
+    #[test]
+    fn required_signers() {
+        let alice = "cosmos19dyl0uyzes4k23lscla02n06fc22h4uqsdwq6z"
+            .parse::<AccountId>()
+            .unwrap();
+        let operator = "cosmos1qypqxpqpqgpsgqgzqvzqzqsrqsqsyqcyy2wt4y"
+            .parse::<AccountId>()
+            .unwrap();
+        let validator = "cosmosvaloper1qypqxpqpqgpsgqgzqvzqzqsrqsqsyqcyp767eh"
+            .parse::<AccountId>()
+            .unwrap();
+
+        let msgs = [
+            MsgWithdrawValidatorCommission {
+                validator_address: validator.clone(),
+            }
+            .to_any()
+            .unwrap(),
+            MsgSend {
+                from_address: alice.clone(),
+                to_address: operator.clone(),
+                amount: vec![],
+            }
+            .to_any()
+            .unwrap(),
+            MsgWithdrawDelegatorReward {
+                delegator_address: operator.clone(),
+                validator_address: validator,
+            }
+            .to_any()
+            .unwrap(),
+        ];
+
+        let body = Body::new(msgs.clone(), "", 0u16);
+        assert_eq!(body.required_signers().unwrap(), [operator, alice]);
+
+        let unknown = Any {
+            type_url: "/example.v1.MsgFoo".to_owned(),
+            value: vec![],
+        };
+        let body = Body::new(msgs.into_iter().chain([unknown]), "", 0u16);
+        assert!(body.required_signers().is_err());
+    }
+}
@@ -0,0 +1,649 @@
+//! Transaction builder.
+
+use super::{
+    AccountNumber, AnyMsg, AuthInfo, Body, Fee, Gas, GasPrice, ModeInfo, Msg, Raw, SequenceNumber,
+    SignMode, SignerInfo,
+};
+use crate::{
+    crypto::{AsyncTxSigner, PublicKey, TxSigner},
+    proto, AccountId, Any, Error, Result,
+};
+use eyre::WrapErr;
+use tendermint::{block, chain};
+
+#[cfg(feature = "grpc")]
+use {super::ServiceClient, std::future::Future};
+
+/// [`Builder`] assembles and signs transactions.
+///
+/// It derives the transaction's [`SignerInfo`]s and sign bytes from the
+/// messages, [`Fee`], and signers it has been configured with, producing a
+/// signed [`Raw`] transaction with [`Builder::sign`], or with
+/// [`Builder::sign_async`] when any of the signers are [`AsyncTxSigner`]s.
+///
+/// Signers are ordered by the addresses which are required to sign the
+/// messages (see [`Body::required_signers`]), followed by the fee payer.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+/// use cosmrs::{
+///     bank::MsgSend,
+///     crypto::secp256k1,
+///     tx::{self, Fee},
+///     tendermint::chain,
+///     Coin,
+/// };
+///
+/// let signing_key = secp256k1::SigningKey::random();
+/// let chain_id = "cosmoshub-4".parse::<chain::Id>()?;
+///
+/// let amount = Coin {
+///     amount: 1_000_000u64.into(),
+///     denom: "uatom".parse()?,
+/// };
+///
+/// let msg_send = MsgSend {
+///     from_address: signing_key.public_key().account_id("cosmos")?,
+///     to_address: "cosmos19dyl0uyzes4k23lscla02n06fc22h4uqsdwq6z".parse()?,
+///     amount: vec![amount.clone()],
+/// };
+///
+/// let account_number = 1;
+/// let sequence = 0;
+///
+/// let raw = tx::Builder::new(chain_id)
+///     .msg(msg_send)?
+///     .memo("example memo")
+///     .fee(Fee::from_amount_and_gas(amount, 100_000u64))
+///     .signer(&signing_key, account_number, sequence)
+///     .sign()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Builder<'a> {
+    /// Chain ID of the chain the transaction targets.
+    chain_id: chain::Id,
+
+    /// Messages to include in the transaction.
+    messages: Vec<Any>,
+
+    /// Addresses required to sign the messages, or `None` if the signers of
+    /// any of the messages are unknown.
+    required_signers: Option<Vec<AccountId>>,
+
+    /// Transaction memo.
+    memo: String,
+
+    /// Block height after which the transaction will not be processed.
+    timeout_height: block::Height,
+
+    /// Transaction fee.
+    fee: Option<Fee>,
+
+    /// Gas limit, used along with the gas price to compute the fee.
+    gas_limit: Option<Gas>,
+
+    /// Gas price, used along with the gas limit to compute the fee.
+    gas_price: Option<GasPrice>,
+
+    /// Fee payer, overriding the payer of the [`Fee`].
+    payer: Option<AccountId>,
+
+    /// Fee granter, overriding the granter of the [`Fee`].
+    granter: Option<AccountId>,
+
+    /// Signing mode used by all signers.
+    sign_mode: SignMode,
+
+    /// Transaction signers, in the order they were added.
+    signers: Vec<SignerEntry<'a>>,
+}
+
+impl<'a> Builder<'a> {
+    /// Create a new [`Builder`] for a transaction targeting the given chain.
+    pub fn new(chain_id: chain::Id) -> Self {
+        Self {
+            chain_id,
+            messages: Vec::new(),
+            required_signers: Some(Vec::new()),
+            memo: String::new(),
+            timeout_height: block::Height::default(),
+            fee: None,
+            gas_limit: None,
+            gas_price: None,
+            payer: None,
+            granter: None,
+            sign_mode: SignMode::Direct,
+            signers: Vec::new(),
+        }
+    }
+
+    /// Add a message to the transaction.
+    pub fn msg(&mut self, msg: impl Msg) -> Result<&mut Self> {
+        let any = msg.to_any()?;
+
+        match msg.signers() {
+            Ok(signers) => self.add_required_signers(signers),
+            Err(err) if matches!(err.downcast_ref(), Some(Error::UnsupportedMsg { .. })) => {
+                self.required_signers = None;
+            }
+            Err(err) => return Err(err),
+        }
+
+        self.messages.push(any);
+        Ok(self)
+    }
+
+    /// Add a message which has already been serialized as [`Any`].
+    ///
+    /// If the message isn't of a type supported by [`AnyMsg`], its signers
+    /// are unknown, and signers are used in the order they were added.
+    pub fn any_msg(&mut self, msg: Any) -> &mut Self {
+        match AnyMsg::from_any(&msg).and_then(|msg| msg.signers()) {
+            Ok(signers) => self.add_required_signers(signers),
+            Err(_) => self.required_signers = None,
+        }
+
+        self.messages.push(msg);
+        self
+    }
+
+    /// Set the transaction memo.
+    pub fn memo(&mut self, memo: impl Into<String>) -> &mut Self {
+        self.memo = memo.into();
+        self
+    }
+
+    /// Set the block height after which the transaction will not be processed.
+    pub fn timeout_height(&mut self, timeout_height: impl Into<block::Height>) -> &mut Self {
+        self.timeout_height = timeout_height.into();
+        self
+    }
+
+    /// Set the transaction [`Fee`].
+    ///
+    /// This takes precedence over a fee computed from a gas limit and price.
+    pub fn fee(&mut self, fee: Fee) -> &mut Self {
+        self.fee = Some(fee);
+        self
+    }
+
+    /// Set the gas limit, which is used along with the gas price set with
+    /// [`Builder::gas_price`] to compute the fee when one hasn't been set
+    /// explicitly.
+    pub fn gas_limit(&mut self, gas_limit: impl Into<Gas>) -> &mut Self {
+        self.gas_limit = Some(gas_limit.into());
+        self
+    }
+
+    /// Set the [`GasPrice`], which is used along with the gas limit set with
+    /// [`Builder::gas_limit`] to compute the fee when one hasn't been set
+    /// explicitly.
+    pub fn gas_price(&mut self, gas_price: GasPrice) -> &mut Self {
+        self.gas_price = Some(gas_price);
+        self
+    }
+
+    /// Set the account which pays the transaction fee.
+    ///
+    /// The payer must be one of the transaction's signers.
+    pub fn payer(&mut self, payer: AccountId) -> &mut Self {
+        self.payer = Some(payer);
+        self
+    }
+
+    /// Set the account which grants the transaction fee via a fee grant.
+    pub fn granter(&mut self, granter: AccountId) -> &mut Self {
+        self.granter = Some(granter);
+        self
+    }
+
+    /// Set the signing mode used by all signers: either `SIGN_MODE_DIRECT`
+    /// (the default) or `SIGN_MODE_LEGACY_AMINO_JSON`.
+    pub fn sign_mode(&mut self, sign_mode: SignMode) -> &mut Self {
+        self.sign_mode = sign_mode;
+        self
+    }
+
+    /// Add a signer to the transaction, along with the account number and
+    /// sequence of its account on the chain the transaction targets.
+    ///
+    /// Signers may be added in any order: they're ordered by the addresses
+    /// required to sign the messages, followed by the fee payer if it isn't
+    /// one of them. If the signers of any message are unknown, signers are
+    /// used in the order they were added, with the first signer paying the
+    /// fee unless a payer is set.
+    pub fn signer(
+        &mut self,
+        signing_key: &'a dyn TxSigner,
+        account_number: AccountNumber,
+        sequence: SequenceNumber,
+    ) -> &mut Self {
+        self.signers
+            .push((Signer::Sync(signing_key), account_number, sequence));
+        self
+    }
+
+    /// Add an [`AsyncTxSigner`], e.g. a remote signer, to the transaction.
+    ///
+    /// Transactions with asynchronous signers must be signed with
+    /// [`Builder::sign_async`]. See [`Builder::signer`] for signer ordering.
+    pub fn async_signer(
+        &mut self,
+        signer: &'a dyn AsyncTxSigner,
+        account_number: AccountNumber,
+        sequence: SequenceNumber,
+    ) -> &mut Self {
+        self.signers
+            .push((Signer::Async(signer), account_number, sequence));
+        self
+    }
+
+    /// Build the transaction [`Body`].
+    pub fn body(&self) -> Body {
+        Body::new(
+            self.messages.iter().cloned(),
+            self.memo.clone(),
+            self.timeout_height,
+        )
+    }
+
+    /// Build the transaction [`AuthInfo`].
+    pub fn auth_info(&self) -> Result<AuthInfo> {
+        let fee = match (&self.fee, self.gas_limit, &self.gas_price) {
+            (Some(fee), _, _) => fee.clone(),
+            (None, Some(gas_limit), Some(gas_price)) => Fee::from_gas_price(gas_limit, gas_price)?,
+            _ => return Err(Error::MissingField { name: "fee" }.into()),
+        };
+
+        self.auth_info_with_fee(fee)
+    }
+
+    /// Build an unsigned transaction suitable for simulation, i.e. with an
+    /// empty signature for each signer.
+    ///
+    /// If no fee has been configured, the transaction has a zero fee.
+    pub fn simulation_tx(&self) -> Result<Raw> {
+        let fee = self.fee.clone().unwrap_or(Fee {
+            amount: Vec::new(),
+            gas_limit: 0u64.into(),
+            payer: None,
+            granter: None,
+        });
+
+        Ok(proto::cosmos::tx::v1beta1::TxRaw {
+            body_bytes: self.body().into_bytes()?,
+            auth_info_bytes: self.auth_info_with_fee(fee)?.into_bytes()?,
+            signatures: vec![Vec::new(); self.signers.len()],
+        }
+        .into())
+    }
+
+    /// Estimate the [`Fee`] for this transaction by simulating it using the
+    /// provided gRPC client.
+    ///
+    /// The gas limit is computed from the gas used during simulation
+    /// multiplied by the given gas adjustment, and the fee amount is computed
+    /// from the gas limit using the given [`GasPrice`]. The estimated fee can
+    /// then be set using [`Builder::fee`].
+    #[cfg(feature = "grpc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "grpc")))]
+    pub fn estimate_fee<'c>(
+        &self,
+        client: &'c mut ServiceClient,
+        gas_price: &GasPrice,
+        gas_adjustment: f64,
+    ) -> impl Future<Output = Result<Fee>> + 'c {
+        let tx = self.simulation_tx();
+        let gas_price = gas_price.clone();
+
+        async move { tx?.simulate(client).await?.fee(&gas_price, gas_adjustment) }
+    }
+
+    /// Build the transaction [`AuthInfo`] with the given [`Fee`].
+    fn auth_info_with_fee(&self, mut fee: Fee) -> Result<AuthInfo> {
+        if self.payer.is_some() {
+            fee.payer = self.payer.clone();
+        }
+
+        if self.granter.is_some() {
+            fee.granter = self.granter.clone();
+        }
+
+        let signer_infos = self
+            .ordered_signers()?
+            .into_iter()
+            .map(|(signer, _, sequence)| SignerInfo {
+                public_key: Some(signer.public_key().into()),
+                mode_info: ModeInfo::single(self.sign_mode),
+                sequence: *sequence,
+            })
+            .collect();
+
+        Ok(AuthInfo { signer_infos, fee })
+    }
+
+    /// Sign the transaction using all of the configured signers, producing
+    /// a [`Raw`] transaction.
+    ///
+    /// Returns an error if no signers have been configured, if neither a fee
+    /// nor a gas limit and price have been configured, or if the signers
+    /// don't match the addresses required to sign the transaction.
+    /// Returns an error if any [`AsyncTxSigner`]s have been configured, in
+    /// which case [`Builder::sign_async`] must be used instead.
+    pub fn sign(&self) -> Result<Raw> {
+        let (unsigned, signers) = self.sign_bytes()?;
+
+        let signatures = signers
+            .iter()
+            .map(|(signer, sign_bytes)| match signer {
+                Signer::Sync(signing_key) => signing_key.sign_tx(sign_bytes),
+                Signer::Async(_) => Err(Error::Signature)
+                    .wrap_err("asynchronous signers require `Builder::sign_async`"),
+            })
+            .collect::<Result<_>>()?;
+
+        let mut raw = proto::cosmos::tx::v1beta1::TxRaw::from(unsigned);
+        raw.signatures = signatures;
+        Ok(raw.into())
+    }
+
+    /// Sign the transaction using all of the configured signers, which may
+    /// include [`AsyncTxSigner`]s, producing a [`Raw`] transaction.
+    ///
+    /// Signers are invoked sequentially, in order. Returns an error under the
+    /// same conditions as [`Builder::sign`], other than for asynchronous signers.
+    pub async fn sign_async(&self) -> Result<Raw> {
+        let (unsigned, signers) = self.sign_bytes()?;
+        let mut signatures = Vec::with_capacity(signers.len());
+
+        for (signer, sign_bytes) in &signers {
+            signatures.push(match signer {
+                Signer::Sync(signing_key) => signing_key.sign_tx(sign_bytes)?,
+                Signer::Async(signer) => signer.sign_tx_async(sign_bytes).await?,
+            });
+        }
+
+        let mut raw = proto::cosmos::tx::v1beta1::TxRaw::from(unsigned);
+        raw.signatures = signatures;
+        Ok(raw.into())
+    }
+
+    /// Build the unsigned transaction and compute the sign bytes for each of
+    /// the configured signers, in order.
+    fn sign_bytes(&self) -> Result<(Raw, Vec<SignerBytes<'a>>)> {
+        if self.signers.is_empty() {
+            return Err(Error::MissingField {
+                name: "signer_infos",
+            }
+            .into());
+        }
+
+        let body = self.body();
+        let auth_info = self.auth_info()?;
+
+        let unsigned = Raw::from(proto::cosmos::tx::v1beta1::TxRaw {
+            body_bytes: body.clone().into_bytes()?,
+            auth_info_bytes: auth_info.clone().into_bytes()?,
+            signatures: Vec::new(),
+        });
+
+        let signers = self
+            .ordered_signers()?
+            .into_iter()
+            .map(|(signer, account_number, sequence)| {
+                let signer_data = SignerData {
+                    chain_id: self.chain_id.clone(),
+                    account_number: *account_number,
+                    sequence: *sequence,
+                };
+
+                let sign_bytes =
+                    unsigned.sign_bytes(&body, &auth_info.fee, self.sign_mode, &signer_data)?;
+
+                Ok((*signer, sign_bytes))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok((unsigned, signers))
+    }
+
+    /// Add the signers of a message to the required signers, unless the
+    /// signers of a previous message are unknown.
+    fn add_required_signers(&mut self, signers: Vec<AccountId>) {
+        if let Some(required_signers) = &mut self.required_signers {
+            for signer in signers {
+                if !required_signers.contains(&signer) {
+                    required_signers.push(signer);
+                }
+            }
+        }
+    }
+
+    /// Get the configured signers in the order the transaction requires, i.e.
+    /// the order of the required signers followed by the fee payer.
+    ///
+    /// If the required signers are unknown or no signers have been added,
+    /// signers are returned in the order they were added.
+    fn ordered_signers(&self) -> Result<Vec<&SignerEntry<'a>>> {
+        let mut expected = match &self.required_signers {
+            Some(required_signers) if !self.signers.is_empty() => required_signers.clone(),
+            _ => return Ok(self.signers.iter().collect()),
+        };
+
+        if let Some(payer) = &self.payer {
+            if !expected.contains(payer) {
+                expected.push(payer.clone());
+            }
+        }
+
+        if expected.len() != self.signers.len() {
+            return Err(Error::SignerCount {
+                expected: expected.len(),
+                found: self.signers.len(),
+            })
+            .wrap_err("signers don't match the transaction's required signers");
+        }
+
+        expected
+            .iter()
+            .map(|address| {
+                for entry in &self.signers {
+                    if entry.0.public_key().account_id(address.prefix())? == *address {
+                        return Ok(entry);
+                    }
+                }
+
+                Err(Error::AccountId {
+                    id: address.to_string(),
+                })
+                .wrap_err("no signer configured for required signer")
+            })
+            .collect()
+    }
+}
+
+/// Transaction signer along with its account number and sequence.
+type SignerEntry<'a> = (Signer<'a>, AccountNumber, SequenceNumber);
+
+/// Transaction signer along with the sign bytes it signs.
+type SignerBytes<'a> = (Signer<'a>, Vec<u8>);
+
+/// Transaction signer, which signs either synchronously or asynchronously.
+#[derive(Clone, Copy)]
+enum Signer<'a> {
+    /// Synchronous signer.
+    Sync(&'a dyn TxSigner),
+
+    /// Asynchronous signer.
+    Async(&'a dyn AsyncTxSigner),
+}
+
+impl Signer<'_> {
+    /// Get the [`PublicKey`] of the signer.
+    fn public_key(&self) -> PublicKey {
+        match self {
+            Signer::Sync(signing_key) => signing_key.public_key(),
+            Signer::Async(signer) => signer.signer_public_key(),
+        }
+    }
+}
+
+/// [`SignerData`] is the information about a signer's account which is
+/// required to sign a transaction, but isn't included in the transaction itself.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignerData {
+    /// Chain ID of the chain the signer's account belongs to.
+    pub chain_id: chain::Id,
+
+    /// Account number of the signer's account in state.
+    pub account_number: AccountNumber,
+
+    /// Sequence number of the signer's account.
+    pub sequence: SequenceNumber,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Builder;
+    use crate::{
+        bank::MsgSend,
+        crypto::secp256k1,
+        tx::{Fee, SignMode},
+        Coin, Error,
+    };
+
+    fn coin(amount: u64) -> Coin {
+        Coin {
+            denom: "uatom".parse().unwrap(),
+            amount: amount.into(),
+        }
+    }
+
+    fn msg_send(from: &secp256k1::SigningKey) -> MsgSend {
+        MsgSend {
+            from_address: from.public_key().account_id("cosmos").unwrap(),
+            to_address: "cosmos19dyl0uyzes4k23lscla02n06fc22h4uqsdwq6z"
+                .parse()
+                .unwrap(),
+            amount: vec![coin(1_000_000)],
+        }
+    }
+
+    #[test]
+    fn sign_with_payer() {
+        let sender = secp256k1::SigningKey::random();
+        let payer = secp256k1::SigningKey::random();
+        let chain_id = "cosmoshub-4".parse::<tendermint::chain::Id>().unwrap();
+
+        for sign_mode in [SignMode::Direct, SignMode::LegacyAminoJson] {
+            let raw = Builder::new(chain_id.clone())
+                .msg(msg_send(&sender))
+                .unwrap()
+                .memo("memo")
+                .fee(Fee::from_amount_and_gas(coin(5_000), 200_000u64))
+                .payer(payer.public_key().account_id("cosmos").unwrap())
+                .sign_mode(sign_mode)
+                .signer(&sender, 1, 2)
+                .signer(&payer, 3, 4)
+                .sign()
+                .unwrap();
+
+            raw.verify_signatures(&chain_id, &[1, 3]).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn sign_async() {
+        let sender = secp256k1::SigningKey::random();
+        let chain_id = "cosmoshub-4".parse::<tendermint::chain::Id>().unwrap();
+
+        let mut builder = Builder::new(chain_id.clone());
+        builder
+            .msg(msg_send(&sender))
+            .unwrap()
+            .fee(Fee::from_amount_and_gas(coin(5_000), 200_000u64))
+            .async_signer(&sender, 1, 0);
+
+        assert!(builder.sign().is_err());
+
+        let raw = builder.sign_async().await.unwrap();
+        raw.verify_signatures(&chain_id, &[1]).unwrap();
+    }
+
+    #[test]
+    fn fee_from_gas_price() {
+        let sender = secp256k1::SigningKey::random();
+        let mut builder = Builder::new("cosmoshub-4".parse().unwrap());
+        builder
+            .msg(msg_send(&sender))
+            .unwrap()
+            .gas_limit(200_001u64)
+            .gas_price("0.025uatom".parse().unwrap());
+
+        let fee = builder.auth_info().unwrap().fee;
+        assert_eq!(fee.gas_limit.value(), 200_001);
+        assert_eq!(fee.amount, [coin(5_001)]);
+
+        let fee = Fee::from_amount_and_gas(coin(6_000), 200_000u64);
+        builder.fee(fee.clone());
+        assert_eq!(builder.auth_info().unwrap().fee, fee);
+    }
+
+    #[test]
+    fn order_signers() {
+        let sender = secp256k1::SigningKey::random();
+        let payer = secp256k1::SigningKey::random();
+        let chain_id = "cosmoshub-4".parse::<tendermint::chain::Id>().unwrap();
+
+        let mut builder = Builder::new(chain_id.clone());
+        builder
+            .msg(msg_send(&sender))
+            .unwrap()
+            .fee(Fee::from_amount_and_gas(coin(5_000), 200_000u64))
+            .payer(payer.public_key().account_id("cosmos").unwrap())
+            .signer(&payer, 3, 4)
+            .signer(&sender, 1, 2);
+
+        let auth_info = builder.auth_info().unwrap();
+        assert_eq!(
+            auth_info.signer_infos[0].public_key,
+            Some(sender.public_key().into())
+        );
+        assert_eq!(auth_info.signer_infos[1].sequence, 4);
+
+        let raw = builder.sign().unwrap();
+        raw.verify_signatures(&chain_id, &[1, 3]).unwrap();
+    }
+
+    #[test]
+    fn reject_wrong_signers() {
+        let sender = secp256k1::SigningKey::random();
+        let other = secp256k1::SigningKey::random();
+
+        let mut builder = Builder::new("cosmoshub-4".parse().unwrap());
+        builder
+            .msg(msg_send(&sender))
+            .unwrap()
+            .fee(Fee::from_amount_and_gas(coin(5_000), 200_000u64))
+            .signer(&other, 1, 0);
+
+        let err = builder.sign().unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<Error>(),
+            Some(Error::AccountId { .. })
+        ));
+
+        builder.signer(&sender, 2, 0);
+        let err = builder.sign().unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<Error>(),
+            Some(Error::SignerCount {
+                expected: 1,
+                found: 2
+            })
+        ));
+    }
+}
@@ -1,6 +1,6 @@
 //! Transaction fees
 
-use super::Gas;
+use super::{Gas, GasPrice};
 use crate::{prost_ext::ParseOptional, proto, AccountId, Coin, ErrorReport, Result};
 
 /// Fee includes the amount of coins paid in fees and the maximum gas to be
@@ -46,6 +46,20 @@ impl Fee {
             granter: None,
         }
     }
+
+    /// Compute the [`Fee`] for the given amount of [`Gas`] at the given
+    /// [`GasPrice`], rounding the amount up to the nearest whole unit.
+    ///
+    /// If the amount is zero, e.g. for a gas price of `0stake`, the fee has no
+    /// coins, as the Cosmos SDK rejects coins with a zero amount.
+    pub fn from_gas_price(gas_limit: impl Into<Gas>, gas_price: &GasPrice) -> Result<Fee> {
+        let gas_limit = gas_limit.into();
+        let amount = gas_price.fee_amount_for(gas_limit)?;
+
+        let mut fee = Fee::from_amount_and_gas(amount, gas_limit);
+        fee.amount.retain(|coin| !coin.amount.is_zero());
+        Ok(fee)
+    }
 }
 
 impl TryFrom<proto::cosmos::tx::v1beta1::Fee> for Fee {
@@ -0,0 +1,70 @@
+//! Gas info.
+
+use super::{Fee, Gas, GasPrice};
+use crate::{proto, Result};
+
+/// [`GasInfo`] describes the gas consumed by a transaction, e.g. when
+/// simulating it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct GasInfo {
+    /// Maximum amount of gas the transaction was allowed to consume.
+    pub gas_wanted: Gas,
+
+    /// Amount of gas actually consumed.
+    pub gas_used: Gas,
+}
+
+impl GasInfo {
+    /// Compute a gas limit from the gas used multiplied by the given gas
+    /// adjustment, which allows for variance between simulation and execution.
+    ///
+    /// Like the Cosmos SDK, the result is truncated to a whole amount of gas.
+    pub fn adjusted_gas_limit(&self, gas_adjustment: f64) -> Gas {
+        ((self.gas_used.value() as f64 * gas_adjustment) as u64).into()
+    }
+
+    /// Compute a [`Fee`] from the gas used multiplied by the given gas
+    /// adjustment, paid at the given [`GasPrice`].
+    pub fn fee(&self, gas_price: &GasPrice, gas_adjustment: f64) -> Result<Fee> {
+        Fee::from_gas_price(self.adjusted_gas_limit(gas_adjustment), gas_price)
+    }
+}
+
+impl From<proto::cosmos::base::abci::v1beta1::GasInfo> for GasInfo {
+    fn from(proto: proto::cosmos::base::abci::v1beta1::GasInfo) -> GasInfo {
+        GasInfo {
+            gas_wanted: proto.gas_wanted.into(),
+            gas_used: proto.gas_used.into(),
+        }
+    }
+}
+
+impl From<GasInfo> for proto::cosmos::base::abci::v1beta1::GasInfo {
+    fn from(info: GasInfo) -> proto::cosmos::base::abci::v1beta1::GasInfo {
+        proto::cosmos::base::abci::v1beta1::GasInfo {
+            gas_wanted: info.gas_wanted.value(),
+            gas_used: info.gas_used.value(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GasInfo;
+
+    #[test]
+    fn fee() {
+        let gas_info = GasInfo {
+            gas_wanted: 0u64.into(),
+            gas_used: 81_234u64.into(),
+        };
+
+        assert_eq!(gas_info.adjusted_gas_limit(1.0).value(), 81_234);
+        assert_eq!(gas_info.adjusted_gas_limit(1.3).value(), 105_604);
+
+        let fee = gas_info.fee(&"0.025uatom".parse().unwrap(), 1.3).unwrap();
+        assert_eq!(fee.gas_limit.value(), 105_604);
+        assert_eq!(fee.amount[0].amount, 2_641u64.into());
+        assert_eq!(fee.amount[0].denom.as_ref(), "uatom");
+    }
+}
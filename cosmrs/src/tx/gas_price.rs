@@ -0,0 +1,367 @@
+//! Gas prices.
+
+use super::{Fee, Gas};
+use crate::{Coin, Denom, Error, ErrorReport, Result};
+use eyre::WrapErr;
+use std::{fmt, str::FromStr};
+
+/// Number of decimal places supported by [`GasPrice`], which matches the
+/// precision of the Cosmos SDK's `sdk.Dec` type.
+const DECIMAL_PLACES: u32 = 18;
+
+/// Scale factor for the fractional part of a [`GasPrice`].
+const SCALE: u128 = 10u128.pow(DECIMAL_PLACES);
+
+/// Price of a single unit of gas in a given denomination, e.g. `0.025uatom`.
+///
+/// Parsed from strings in the same format as an entry of a node's
+/// `minimum-gas-prices`.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub struct GasPrice {
+    /// Price per unit of gas in units of 10^-18 of the denomination.
+    amount: u128,
+
+    /// Denomination.
+    denom: Denom,
+}
+
+impl GasPrice {
+    /// Get the denomination of this [`GasPrice`].
+    pub fn denom(&self) -> &Denom {
+        &self.denom
+    }
+
+    /// Compute the fee amount required for the given amount of [`Gas`] at this
+    /// price.
+    ///
+    /// The amount is rounded up to the nearest whole unit of the denomination,
+    /// which matches the minimum gas price check performed by the Cosmos SDK
+    /// when admitting transactions to the mempool.
+    pub fn fee_amount_for(&self, gas: impl Into<Gas>) -> Result<Coin> {
+        Ok(Coin {
+            denom: self.denom.clone(),
+            amount: self.fee_amount(gas.into().value())?.into(),
+        })
+    }
+
+    /// Compute the fee amount for the given amount of gas, rounding up to the
+    /// nearest whole unit of the denomination.
+    fn fee_amount(&self, gas: u64) -> Result<u64> {
+        let gas = u128::from(gas);
+        let whole = (self.amount / SCALE).checked_mul(gas);
+        let fractional = ((self.amount % SCALE) * gas + SCALE - 1) / SCALE;
+
+        whole
+            .and_then(|whole| whole.checked_add(fractional))
+            .and_then(|amount| u64::try_from(amount).ok())
+            .ok_or_else(|| {
+                Error::Decimal {
+                    value: format!("{} * {}", self, gas),
+                }
+                .into()
+            })
+    }
+}
+
+impl FromStr for GasPrice {
+    type Err = ErrorReport;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let split = s
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(s.len());
+        let (amount, denom) = s.split_at(split);
+
+        let invalid_amount = || Error::Decimal {
+            value: amount.to_owned(),
+        };
+
+        let (whole, fractional) = match amount.split_once('.') {
+            Some((whole, fractional)) => (whole, fractional),
+            None => (amount, ""),
+        };
+
+        if (whole.is_empty() && fractional.is_empty())
+            || amount.ends_with('.')
+            || fractional.contains('.')
+            || fractional.len() > DECIMAL_PLACES as usize
+        {
+            return Err(invalid_amount().into());
+        }
+
+        let whole = match whole {
+            "" => 0,
+            whole => whole.parse::<u128>().map_err(|_| invalid_amount())?,
+        };
+
+        let fractional = match fractional {
+            "" => 0,
+            fractional => {
+                fractional.parse::<u128>().map_err(|_| invalid_amount())?
+                    * 10u128.pow(DECIMAL_PLACES - fractional.len() as u32)
+            }
+        };
+
+        let amount = whole
+            .checked_mul(SCALE)
+            .and_then(|whole| whole.checked_add(fractional))
+            .ok_or_else(invalid_amount)?;
+
+        Ok(Self {
+            amount,
+            denom: denom.trim_start().parse()?,
+        })
+    }
+}
+
+impl fmt::Display for GasPrice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let whole = self.amount / SCALE;
+        let fractional = self.amount % SCALE;
+
+        if fractional == 0 {
+            write!(f, "{}{}", whole, self.denom)
+        } else {
+            let fractional = format!("{:018}", fractional);
+            write!(
+                f,
+                "{}.{}{}",
+                whole,
+                fractional.trim_end_matches('0'),
+                self.denom
+            )
+        }
+    }
+}
+
+/// Set of [`GasPrice`]s in different denominations, any of which can be
+/// used to pay fees, e.g. `0.025uatom,0.1stake`.
+///
+/// Parsed from strings in the same format as a node's `minimum-gas-prices`.
+/// Like the Cosmos SDK, the gas prices are sorted by denomination.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GasPrices(Vec<GasPrice>);
+
+impl GasPrices {
+    /// Get the [`GasPrice`] for the given denomination, if present.
+    pub fn get(&self, denom: &Denom) -> Option<&GasPrice> {
+        self.0.iter().find(|gas_price| &gas_price.denom == denom)
+    }
+
+    /// Iterate over the [`GasPrice`]s in this set.
+    pub fn iter(&self) -> impl Iterator<Item = &GasPrice> {
+        self.0.iter()
+    }
+
+    /// Is this set of gas prices empty?
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Compute the [`Fee`] for the given amount of [`Gas`], paid in the
+    /// given denomination.
+    pub fn fee(&self, gas_limit: impl Into<Gas>, denom: &Denom) -> Result<Fee> {
+        let gas_price = self.get(denom).ok_or_else(|| Error::Denom {
+            name: denom.to_string(),
+        })?;
+
+        Fee::from_gas_price(gas_limit, gas_price)
+    }
+
+    /// Check whether the given [`Fee`] satisfies these gas prices, i.e. the
+    /// fee amount in at least one of the denominations is at least the amount
+    /// required for the fee's gas limit.
+    ///
+    /// This is the same check the Cosmos SDK performs against a node's
+    /// `minimum-gas-prices`. An empty set of gas prices is satisfied by any fee.
+    pub fn is_satisfied_by(&self, fee: &Fee) -> Result<bool> {
+        if self.is_empty() {
+            return Ok(true);
+        }
+
+        for gas_price in &self.0 {
+            let required = gas_price.fee_amount_for(fee.gas_limit)?;
+
+            // A zero gas price doesn't require any fee
+            if required.amount.is_zero() {
+                return Ok(true);
+            }
+
+            if fee
+                .amount
+                .iter()
+                .any(|coin| coin.denom == required.denom && coin.amount >= required.amount)
+            {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+impl FromStr for GasPrices {
+    type Err = ErrorReport;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+
+        if s.is_empty() {
+            return Ok(Self::default());
+        }
+
+        let mut gas_prices = s
+            .split(',')
+            .map(str::parse)
+            .collect::<Result<Vec<GasPrice>>>()?;
+
+        gas_prices.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+        for pair in gas_prices.windows(2) {
+            if pair[0].denom == pair[1].denom {
+                return Err(Error::Denom {
+                    name: pair[0].denom.to_string(),
+                })
+                .wrap_err("duplicate gas price denomination");
+            }
+        }
+
+        Ok(Self(gas_prices))
+    }
+}
+
+impl fmt::Display for GasPrices {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, gas_price) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+
+            write!(f, "{}", gas_price)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<GasPrice> for GasPrices {
+    fn from(gas_price: GasPrice) -> GasPrices {
+        GasPrices(vec![gas_price])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GasPrice, GasPrices};
+    use crate::{tx::Fee, Coin};
+
+    #[test]
+    fn parse() {
+        let gas_price = "0.025uatom".parse::<GasPrice>().unwrap();
+        assert_eq!(gas_price.denom().as_ref(), "uatom");
+        assert_eq!(gas_price.to_string(), "0.025uatom");
+
+        assert_eq!(
+            "12 ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+                .parse::<GasPrice>()
+                .unwrap()
+                .to_string(),
+            "12ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+        );
+
+        assert_eq!(
+            ".5stake".parse::<GasPrice>().unwrap().to_string(),
+            "0.5stake"
+        );
+
+        for invalid in [
+            "",
+            "uatom",
+            "1.uatom",
+            "0.0.1uatom",
+            "0.025",
+            "0.0000000000000000001uatom",
+        ] {
+            assert!(invalid.parse::<GasPrice>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn fee_amount() {
+        let gas_price = "0.025uatom".parse::<GasPrice>().unwrap();
+        assert_eq!(gas_price.fee_amount(200_000).unwrap(), 5_000);
+        assert_eq!(gas_price.fee_amount(200_001).unwrap(), 5_001);
+        assert_eq!(gas_price.fee_amount(0).unwrap(), 0);
+
+        // Fractional amounts always round up, as in the SDK's mempool check
+        let gas_price = "0.000000000000000001uatom".parse::<GasPrice>().unwrap();
+        assert_eq!(gas_price.fee_amount(1).unwrap(), 1);
+
+        let gas_price = "1.5uatom".parse::<GasPrice>().unwrap();
+        assert_eq!(gas_price.fee_amount(3).unwrap(), 5);
+
+        let gas_price = "1000000000000stake".parse::<GasPrice>().unwrap();
+        assert!(gas_price.fee_amount(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn gas_prices() {
+        let gas_prices = "0.1stake, 0.025uatom".parse::<GasPrices>().unwrap();
+        assert_eq!(gas_prices.to_string(), "0.1stake,0.025uatom");
+        assert_eq!(
+            "0.025uatom,0.1stake".parse::<GasPrices>().unwrap(),
+            gas_prices
+        );
+        assert!("".parse::<GasPrices>().unwrap().is_empty());
+        assert!("0.1uatom,0.2uatom".parse::<GasPrices>().is_err());
+
+        let fee = gas_prices
+            .fee(200_000u64, &"uatom".parse().unwrap())
+            .unwrap();
+        assert_eq!(fee.amount, [coin(5_000, "uatom")]);
+        assert!(gas_prices
+            .fee(200_000u64, &"uosmo".parse().unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn is_satisfied_by() {
+        let gas_prices = "0.025uatom,0.1stake".parse::<GasPrices>().unwrap();
+
+        let fee = Fee::from_amount_and_gas(coin(5_000, "uatom"), 200_000u64);
+        assert!(gas_prices.is_satisfied_by(&fee).unwrap());
+
+        let fee = Fee::from_amount_and_gas(coin(4_999, "uatom"), 200_000u64);
+        assert!(!gas_prices.is_satisfied_by(&fee).unwrap());
+
+        let mut fee = Fee::from_amount_and_gas(coin(20_000, "stake"), 200_000u64);
+        fee.amount.push(coin(1, "uatom"));
+        assert!(gas_prices.is_satisfied_by(&fee).unwrap());
+
+        let fee = Fee::from_amount_and_gas(coin(1_000_000, "uosmo"), 200_000u64);
+        assert!(!gas_prices.is_satisfied_by(&fee).unwrap());
+        assert!(GasPrices::default().is_satisfied_by(&fee).unwrap());
+    }
+
+    #[test]
+    fn zero_gas_price() {
+        let gas_price = "0stake".parse::<GasPrice>().unwrap();
+        let fee = Fee::from_gas_price(200_000u64, &gas_price).unwrap();
+        assert!(fee.amount.is_empty());
+        assert_eq!(fee.gas_limit, 200_000u64.into());
+
+        // A zero gas price in any denomination is satisfied by any fee
+        let gas_prices = "0.025uatom,0stake".parse::<GasPrices>().unwrap();
+        assert!(gas_prices
+            .is_satisfied_by(&Fee::from_gas_price(200_000u64, &gas_price).unwrap())
+            .unwrap());
+    }
+
+    fn coin(amount: u64, denom: &str) -> Coin {
+        Coin {
+            denom: denom.parse().unwrap(),
+            amount: amount.into(),
+        }
+    }
+}
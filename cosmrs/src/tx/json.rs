@@ -0,0 +1,459 @@
+//! Protobuf JSON encoding of transactions.
+//!
+//! This is the JSON format used by the Cosmos SDK CLI, e.g. `tx sign`,
+//! `tx encode`, and `tx decode`, where messages and public keys are encoded
+//! as `Any` with an `@type` field containing the type URL.
+
+use super::{
+    mode_info, AnyMsg, AuthInfo, Body, Fee, ModeInfo, SignMode, SignerInfo, SignerPublicKey, Tx,
+};
+use crate::{
+    crypto::{LegacyAminoMultisig, PublicKey},
+    proto, serializers, AccountId, Any, Coin, Error, Result,
+};
+use eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tendermint::block;
+
+/// Encode a [`Tx`] as Protobuf JSON.
+pub(super) fn encode(tx: &Tx) -> Result<Value> {
+    let json = TxJson {
+        body: BodyJson {
+            messages: tx
+                .body
+                .messages
+                .iter()
+                .map(msg_to_json)
+                .collect::<Result<_>>()?,
+            memo: tx.body.memo.clone(),
+            timeout_height: tx.body.timeout_height.value(),
+            extension_options: tx
+                .body
+                .extension_options
+                .iter()
+                .map(msg_to_json)
+                .collect::<Result<_>>()?,
+            non_critical_extension_options: tx
+                .body
+                .non_critical_extension_options
+                .iter()
+                .map(msg_to_json)
+                .collect::<Result<_>>()?,
+        },
+        auth_info: AuthInfoJson {
+            signer_infos: tx
+                .auth_info
+                .signer_infos
+                .iter()
+                .map(|signer_info| {
+                    Ok(SignerInfoJson {
+                        public_key: signer_info
+                            .public_key
+                            .as_ref()
+                            .map(public_key_to_json)
+                            .transpose()?,
+                        mode_info: (&signer_info.mode_info).into(),
+                        sequence: signer_info.sequence,
+                    })
+                })
+                .collect::<Result<_>>()?,
+            fee: FeeJson {
+                amount: tx.auth_info.fee.amount.clone(),
+                gas_limit: tx.auth_info.fee.gas_limit.value(),
+                payer: tx.auth_info.fee.payer.clone(),
+                granter: tx.auth_info.fee.granter.clone(),
+            },
+        },
+        signatures: tx.signatures.clone(),
+    };
+
+    Ok(serde_json::to_value(json)?)
+}
+
+/// Decode a [`Tx`] from Protobuf JSON.
+pub(super) fn decode(value: Value) -> Result<Tx> {
+    let json = serde_json::from_value::<TxJson>(value)?;
+
+    let mut body = Body::new(
+        json.body
+            .messages
+            .into_iter()
+            .map(msg_from_json)
+            .collect::<Result<Vec<_>>>()?,
+        json.body.memo,
+        block::Height::try_from(json.body.timeout_height)?,
+    );
+
+    body.extension_options = json
+        .body
+        .extension_options
+        .into_iter()
+        .map(msg_from_json)
+        .collect::<Result<_>>()?;
+
+    body.non_critical_extension_options = json
+        .body
+        .non_critical_extension_options
+        .into_iter()
+        .map(msg_from_json)
+        .collect::<Result<_>>()?;
+
+    let signer_infos = json
+        .auth_info
+        .signer_infos
+        .into_iter()
+        .map(|signer_info| {
+            Ok(SignerInfo {
+                public_key: signer_info
+                    .public_key
+                    .map(public_key_from_json)
+                    .transpose()?,
+                mode_info: signer_info.mode_info.try_into()?,
+                sequence: signer_info.sequence,
+            })
+        })
+        .collect::<Result<_>>()?;
+
+    let fee = Fee {
+        amount: json.auth_info.fee.amount,
+        gas_limit: json.auth_info.fee.gas_limit.into(),
+        payer: json.auth_info.fee.payer,
+        granter: json.auth_info.fee.granter,
+    };
+
+    Ok(Tx {
+        body,
+        auth_info: AuthInfo { signer_infos, fee },
+        signatures: json.signatures,
+    })
+}
+
+/// Protobuf JSON encoding of [`Tx`].
+#[derive(Default, Deserialize, Serialize)]
+#[serde(default)]
+struct TxJson {
+    body: BodyJson,
+    auth_info: AuthInfoJson,
+    #[serde(with = "serializers::base64_vec")]
+    signatures: Vec<Vec<u8>>,
+}
+
+/// Protobuf JSON encoding of [`Body`].
+#[derive(Default, Deserialize, Serialize)]
+#[serde(default)]
+struct BodyJson {
+    messages: Vec<Value>,
+    memo: String,
+    #[serde(with = "serializers::u64_string")]
+    timeout_height: u64,
+    extension_options: Vec<Value>,
+    non_critical_extension_options: Vec<Value>,
+}
+
+/// Protobuf JSON encoding of [`AuthInfo`].
+#[derive(Default, Deserialize, Serialize)]
+#[serde(default)]
+struct AuthInfoJson {
+    signer_infos: Vec<SignerInfoJson>,
+    fee: FeeJson,
+}
+
+/// Protobuf JSON encoding of [`SignerInfo`].
+#[derive(Deserialize, Serialize)]
+struct SignerInfoJson {
+    #[serde(default)]
+    public_key: Option<Value>,
+    mode_info: ModeInfoJson,
+    #[serde(default, with = "serializers::u64_string")]
+    sequence: u64,
+}
+
+/// Protobuf JSON encoding of [`ModeInfo`].
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ModeInfoJson {
+    Single {
+        #[serde(with = "sign_mode")]
+        mode: SignMode,
+    },
+    Multi {
+        bitarray: CompactBitArrayJson,
+        #[serde(default)]
+        mode_infos: Vec<ModeInfoJson>,
+    },
+}
+
+impl From<&ModeInfo> for ModeInfoJson {
+    fn from(mode_info: &ModeInfo) -> ModeInfoJson {
+        match mode_info {
+            ModeInfo::Single(single) => ModeInfoJson::Single { mode: single.mode },
+            ModeInfo::Multi(multi) => {
+                let bitarray = proto::cosmos::crypto::multisig::v1beta1::CompactBitArray::from(
+                    multi.bitarray.clone(),
+                );
+
+                ModeInfoJson::Multi {
+                    bitarray: CompactBitArrayJson {
+                        extra_bits_stored: bitarray.extra_bits_stored,
+                        elems: bitarray.elems,
+                    },
+                    mode_infos: multi.mode_infos.iter().map(Into::into).collect(),
+                }
+            }
+        }
+    }
+}
+
+impl TryFrom<ModeInfoJson> for ModeInfo {
+    type Error = crate::ErrorReport;
+
+    fn try_from(json: ModeInfoJson) -> Result<ModeInfo> {
+        Ok(match json {
+            ModeInfoJson::Single { mode } => ModeInfo::single(mode),
+            ModeInfoJson::Multi {
+                bitarray,
+                mode_infos,
+            } => mode_info::Multi {
+                bitarray: proto::cosmos::crypto::multisig::v1beta1::CompactBitArray {
+                    extra_bits_stored: bitarray.extra_bits_stored,
+                    elems: bitarray.elems,
+                }
+                .into(),
+                mode_infos: mode_infos
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_>>()?,
+            }
+            .into(),
+        })
+    }
+}
+
+/// Protobuf JSON encoding of a compact bit array.
+#[derive(Deserialize, Serialize)]
+struct CompactBitArrayJson {
+    #[serde(default)]
+    extra_bits_stored: u32,
+    #[serde(default, with = "serializers::base64_bytes")]
+    elems: Vec<u8>,
+}
+
+/// Protobuf JSON encoding of [`Fee`].
+#[derive(Default, Deserialize, Serialize)]
+#[serde(default)]
+struct FeeJson {
+    amount: Vec<Coin>,
+    #[serde(with = "serializers::u64_string")]
+    gas_limit: u64,
+    #[serde(with = "serializers::optional_string")]
+    payer: Option<AccountId>,
+    #[serde(with = "serializers::optional_string")]
+    granter: Option<AccountId>,
+}
+
+/// Encode a [`SignerPublicKey`] as Protobuf JSON.
+fn public_key_to_json(public_key: &SignerPublicKey) -> Result<Value> {
+    match public_key {
+        SignerPublicKey::Single(public_key) => Ok(serde_json::to_value(public_key)?),
+        SignerPublicKey::LegacyAminoMultisig(multisig) => Ok(json!({
+            "@type": LegacyAminoMultisig::TYPE_URL,
+            "threshold": multisig.threshold,
+            "public_keys": multisig
+                .public_keys
+                .iter()
+                .map(public_key_to_json)
+                .collect::<Result<Vec<_>>>()?,
+        })),
+        SignerPublicKey::Any(any) => Err(Error::Crypto)
+            .wrap_err_with(|| format!("unsupported public key type: {}", any.type_url)),
+    }
+}
+
+/// Decode a [`SignerPublicKey`] from Protobuf JSON.
+fn public_key_from_json(value: Value) -> Result<SignerPublicKey> {
+    if value["@type"] != LegacyAminoMultisig::TYPE_URL {
+        return Ok(serde_json::from_value::<PublicKey>(value)?.into());
+    }
+
+    #[derive(Deserialize)]
+    struct LegacyAminoMultisigJson {
+        threshold: u32,
+        public_keys: Vec<Value>,
+    }
+
+    let json = serde_json::from_value::<LegacyAminoMultisigJson>(value)?;
+
+    // Members may be nested multisigs
+    Ok(LegacyAminoMultisig {
+        threshold: json.threshold,
+        public_keys: json
+            .public_keys
+            .into_iter()
+            .map(public_key_from_json)
+            .collect::<Result<Vec<_>>>()?,
+    }
+    .into())
+}
+
+/// Encode a message [`Any`] as Protobuf JSON.
+fn msg_to_json(any: &Any) -> Result<Value> {
+    AnyMsg::from_any(any)?.to_json()
+}
+
+/// Decode a message [`Any`] from Protobuf JSON.
+fn msg_from_json(value: Value) -> Result<Any> {
+    let type_url = value["@type"]
+        .as_str()
+        .ok_or(Error::MissingField { name: "@type" })?
+        .to_owned();
+
+    AnyMsg::from_json(&type_url, value)?.to_any()
+}
+
+/// Serialize [`SignMode`] using the names of its Protobuf enum values.
+mod sign_mode {
+    use super::SignMode;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        sign_mode: &SignMode,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(match sign_mode {
+            SignMode::Unspecified => "SIGN_MODE_UNSPECIFIED",
+            SignMode::Direct => "SIGN_MODE_DIRECT",
+            SignMode::Textual => "SIGN_MODE_TEXTUAL",
+            SignMode::LegacyAminoJson => "SIGN_MODE_LEGACY_AMINO_JSON",
+            SignMode::Eip191 => "SIGN_MODE_EIP_191",
+        })
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<SignMode, D::Error> {
+        match String::deserialize(deserializer)?.as_str() {
+            "SIGN_MODE_UNSPECIFIED" => Ok(SignMode::Unspecified),
+            "SIGN_MODE_DIRECT" => Ok(SignMode::Direct),
+            "SIGN_MODE_TEXTUAL" => Ok(SignMode::Textual),
+            "SIGN_MODE_LEGACY_AMINO_JSON" => Ok(SignMode::LegacyAminoJson),
+            "SIGN_MODE_EIP_191" => Ok(SignMode::Eip191),
+            other => Err(de::Error::custom(format!("invalid sign mode: {}", other))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tx::{ModeInfo, SignMode, SignerPublicKey, Tx};
+    use serde_json::Value;
+
+    /// Transaction in the format output by `gaiad tx sign`.
+    const EXAMPLE_JSON: &str = r#"{
+        "body": {
+            "messages": [
+                {
+                    "@type": "/cosmos.bank.v1beta1.MsgSend",
+                    "from_address": "cosmos1qyqszqgpqyqszqgpqyqszqgpqyqszqgpjnp7du",
+                    "to_address": "cosmos19dyl0uyzes4k23lscla02n06fc22h4uqsdwq6z",
+                    "amount": [{"denom": "uatom", "amount": "1000000"}]
+                }
+            ],
+            "memo": "example memo",
+            "timeout_height": "9001",
+            "extension_options": [],
+            "non_critical_extension_options": []
+        },
+        "auth_info": {
+            "signer_infos": [
+                {
+                    "public_key": {
+                        "@type": "/cosmos.crypto.secp256k1.PubKey",
+                        "key": "Anm+Zn753LusVaBilc6HCwcCm/zbLc4o2VnygVsW+BeY"
+                    },
+                    "mode_info": {"single": {"mode": "SIGN_MODE_LEGACY_AMINO_JSON"}},
+                    "sequence": "3"
+                }
+            ],
+            "fee": {
+                "amount": [{"denom": "uatom", "amount": "5000"}],
+                "gas_limit": "200000",
+                "payer": "",
+                "granter": "cosmos19dyl0uyzes4k23lscla02n06fc22h4uqsdwq6z"
+            }
+        },
+        "signatures": ["AQID"]
+    }"#;
+
+    #[test]
+    fn json_round_trip() {
+        let tx = Tx::from_json(EXAMPLE_JSON).unwrap();
+
+        assert_eq!(tx.body.messages.len(), 1);
+        assert_eq!(tx.body.memo, "example memo");
+        assert_eq!(tx.body.timeout_height.value(), 9001);
+        assert_eq!(tx.auth_info.fee.gas_limit.value(), 200_000);
+        assert_eq!(tx.auth_info.fee.payer, None);
+        assert!(tx.auth_info.fee.granter.is_some());
+        assert_eq!(tx.signatures, [vec![1, 2, 3]]);
+
+        let signer_info = &tx.auth_info.signer_infos[0];
+        assert_eq!(signer_info.sequence, 3);
+        assert_eq!(
+            signer_info.mode_info,
+            ModeInfo::single(SignMode::LegacyAminoJson)
+        );
+        assert!(matches!(
+            signer_info.public_key,
+            Some(SignerPublicKey::Single(_))
+        ));
+
+        // Protobuf round trip
+        let tx = Tx::from_bytes(&tx.clone().into_raw().unwrap().to_bytes().unwrap()).unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<Value>(&tx.to_json().unwrap()).unwrap(),
+            serde_json::from_str::<Value>(EXAMPLE_JSON).unwrap()
+        );
+    }
+
+    #[test]
+    fn unsupported_msg() {
+        let json = EXAMPLE_JSON.replace("/cosmos.bank.v1beta1.MsgSend", "/example.v1.MsgFoo");
+        assert!(Tx::from_json(&json).is_err());
+    }
+
+    #[cfg(feature = "cosmwasm")]
+    #[test]
+    fn cosmwasm_round_trip() {
+        let json = serde_json::json!({
+            "body": {
+                "messages": [{
+                    "@type": "/cosmwasm.wasm.v1.MsgExecuteContract",
+                    "sender": "cosmos1qyqszqgpqyqszqgpqyqszqgpqyqszqgpjnp7du",
+                    "contract": "cosmos19dyl0uyzes4k23lscla02n06fc22h4uqsdwq6z",
+                    "msg": {"transfer": {"amount": "10"}},
+                    "funds": []
+                }],
+                "memo": "",
+                "timeout_height": "0",
+                "extension_options": [],
+                "non_critical_extension_options": []
+            },
+            "auth_info": {
+                "signer_infos": [],
+                "fee": {
+                    "amount": [],
+                    "gas_limit": "0",
+                    "payer": "",
+                    "granter": ""
+                }
+            },
+            "signatures": []
+        });
+
+        let tx = Tx::from_json(&json.to_string()).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(&tx.to_json().unwrap()).unwrap(),
+            json
+        );
+    }
+}
@@ -1,6 +1,6 @@
 //! Transaction messages
 
-use crate::{prost_ext::MessageExt, proto, Any, Error, ErrorReport, Result};
+use crate::{prost_ext::MessageExt, proto, AccountId, Any, Error, ErrorReport, Result};
 
 /// Message types.
 ///
@@ -14,6 +14,27 @@ pub trait Msg:
     /// Protocol Buffers type
     type Proto: MsgProto;
 
+    /// Get the addresses of the accounts which must sign this message,
+    /// i.e. the equivalent of `GetSigners` in the Cosmos SDK.
+    ///
+    /// The default implementation returns an [`Error::UnsupportedMsg`] error,
+    /// as the signers of the message are unknown.
+    fn signers(&self) -> Result<Vec<AccountId>> {
+        Err(Error::UnsupportedMsg {
+            type_url: Self::Proto::TYPE_URL.to_owned(),
+        }
+        .into())
+    }
+
+    /// Perform stateless validation of this message, i.e. the equivalent of
+    /// `ValidateBasic` in the Cosmos SDK.
+    ///
+    /// The default implementation performs no additional validation beyond
+    /// what's already checked when parsing the message.
+    fn validate_basic(&self) -> Result<()> {
+        Ok(())
+    }
+
     /// Parse this message proto from [`Any`].
     fn from_any(any: &Any) -> Result<Self> {
         Self::Proto::from_any(any)?.try_into()
@@ -30,6 +51,27 @@ pub trait Msg:
     }
 }
 
+/// Message types which support the legacy Amino JSON encoding.
+///
+/// This encoding is used to compute the sign bytes for
+/// `SIGN_MODE_LEGACY_AMINO_JSON` (see [`StdSignDoc`][`super::StdSignDoc`]).
+pub trait LegacyAminoMsg: Msg {
+    /// Amino type name, e.g. `cosmos-sdk/MsgSend`.
+    const AMINO_TYPE: &'static str;
+
+    /// Serialize the fields of this message as Amino JSON.
+    fn to_amino_value(&self) -> Result<serde_json::Value>;
+
+    /// Serialize this message as an Amino JSON object containing its `type`
+    /// and `value`.
+    fn to_amino_json(&self) -> Result<serde_json::Value> {
+        Ok(serde_json::json!({
+            "type": Self::AMINO_TYPE,
+            "value": self.to_amino_value()?,
+        }))
+    }
+}
+
 /// Proto types which can be used as a [`Msg`].
 pub trait MsgProto: Default + MessageExt + Sized {
     /// Type URL value
@@ -118,3 +160,43 @@ impl MsgProto for proto::cosmwasm::wasm::v1::MsgUpdateAdmin {
 impl MsgProto for proto::cosmwasm::wasm::v1::MsgClearAdmin {
     const TYPE_URL: &'static str = "/cosmwasm.wasm.v1.MsgClearAdmin";
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Msg;
+    use crate::{proto, Error, ErrorReport, Result};
+
+    /// Example of a downstream message type which doesn't impl `signers`.
+    #[derive(Clone, Debug)]
+    struct DownstreamMsg(proto::cosmos::bank::v1beta1::MsgSend);
+
+    impl Msg for DownstreamMsg {
+        type Proto = proto::cosmos::bank::v1beta1::MsgSend;
+    }
+
+    impl TryFrom<proto::cosmos::bank::v1beta1::MsgSend> for DownstreamMsg {
+        type Error = ErrorReport;
+
+        fn try_from(proto: proto::cosmos::bank::v1beta1::MsgSend) -> Result<DownstreamMsg> {
+            Ok(DownstreamMsg(proto))
+        }
+    }
+
+    impl From<DownstreamMsg> for proto::cosmos::bank::v1beta1::MsgSend {
+        fn from(msg: DownstreamMsg) -> proto::cosmos::bank::v1beta1::MsgSend {
+            msg.0
+        }
+    }
+
+    #[test]
+    fn default_signers() {
+        let msg = DownstreamMsg(Default::default());
+        let err = msg.signers().unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<Error>(),
+            Some(Error::UnsupportedMsg { type_url }) if type_url == "/cosmos.bank.v1beta1.MsgSend"
+        ));
+        assert!(msg.validate_basic().is_ok());
+    }
+}
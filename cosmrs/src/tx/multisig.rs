@@ -0,0 +1,270 @@
+//! Multisig signatures.
+
+use super::{
+    mode_info::{self, ModeInfo},
+    SequenceNumber, SignMode, SignatureBytes, SignerInfo, SignerPublicKey,
+};
+use crate::{
+    crypto::{CompactBitArray, LegacyAminoMultisig, PublicKey},
+    prost_ext::MessageExt,
+    proto, Error, Result,
+};
+use eyre::WrapErr;
+
+/// [`MultisigSignature`] combines the signatures of the individual members of a
+/// [`LegacyAminoMultisig`] key into a single signature.
+///
+/// Signatures are collected from the members of the multisig (typically by
+/// signing a [`StdSignDoc`][`super::StdSignDoc`]) and then combined using
+/// [`MultisigSignature::to_bytes`], which produces the signature to be placed
+/// in [`Tx::signatures`][`super::Tx::signatures`], along with
+/// [`MultisigSignature::signer_info`], which produces the corresponding
+/// [`SignerInfo`].
+///
+/// Members of the multisig which are themselves multisig keys sign with a
+/// nested [`MultisigSignature`], which is added with
+/// [`MultisigSignature::set_nested`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MultisigSignature {
+    /// Multisig public key.
+    public_key: LegacyAminoMultisig,
+
+    /// Signatures of members of the multisig, indexed by their position in
+    /// the multisig's public keys.
+    signatures: Vec<Option<(ModeInfo, SignatureBytes)>>,
+}
+
+impl MultisigSignature {
+    /// Create a new [`MultisigSignature`] for the given multisig key, which
+    /// initially has no member signatures.
+    pub fn new(public_key: LegacyAminoMultisig) -> Self {
+        let signatures = vec![None; public_key.public_keys.len()];
+
+        Self {
+            public_key,
+            signatures,
+        }
+    }
+
+    /// Get the multisig public key.
+    pub fn public_key(&self) -> &LegacyAminoMultisig {
+        &self.public_key
+    }
+
+    /// Add a signature produced by the member of the multisig with the given
+    /// public key using `SIGN_MODE_LEGACY_AMINO_JSON`.
+    pub fn add_signature(
+        &mut self,
+        public_key: &PublicKey,
+        signature: impl Into<SignatureBytes>,
+    ) -> Result<()> {
+        let index = self
+            .public_key
+            .public_keys
+            .iter()
+            .position(|pk| pk.single() == Some(public_key))
+            .ok_or(Error::Crypto)
+            .wrap_err("public key is not a member of the multisig")?;
+
+        self.set_signature(index, SignMode::LegacyAminoJson, signature)
+    }
+
+    /// Set the signature of the member of the multisig at the given index,
+    /// produced using the given [`SignMode`].
+    ///
+    /// Returns an error if the member is a nested multisig key, whose
+    /// signature must be set using [`MultisigSignature::set_nested`].
+    pub fn set_signature(
+        &mut self,
+        index: usize,
+        sign_mode: SignMode,
+        signature: impl Into<SignatureBytes>,
+    ) -> Result<()> {
+        if let Some(SignerPublicKey::LegacyAminoMultisig(_)) =
+            self.public_key.public_keys.get(index)
+        {
+            return Err(Error::Crypto)
+                .wrap_err_with(|| format!("multisig member {} is a nested multisig", index));
+        }
+
+        self.set(index, ModeInfo::single(sign_mode), signature.into())
+    }
+
+    /// Set the signature of the member of the multisig at the given index,
+    /// which must be a nested multisig key, to the combined signature of the
+    /// given [`MultisigSignature`] for that key.
+    pub fn set_nested(&mut self, index: usize, nested: &MultisigSignature) -> Result<()> {
+        match self.public_key.public_keys.get(index) {
+            Some(SignerPublicKey::LegacyAminoMultisig(public_key))
+                if *public_key == nested.public_key => {}
+            Some(_) => {
+                return Err(Error::Crypto).wrap_err_with(|| {
+                    format!(
+                        "multisig member {} doesn't match the nested multisig",
+                        index
+                    )
+                })
+            }
+            None => {} // handled by `set`
+        }
+
+        self.set(index, nested.mode_info(), nested.to_bytes()?)
+    }
+
+    /// Number of members of the multisig which have signed.
+    pub fn signature_count(&self) -> usize {
+        self.signatures.iter().filter(|sig| sig.is_some()).count()
+    }
+
+    /// Has the multisig threshold been reached?
+    pub fn is_complete(&self) -> bool {
+        self.signature_count() >= self.public_key.threshold as usize
+    }
+
+    /// Get the [`ModeInfo`] for this multisig signature, which contains a
+    /// bit array indicating which members of the multisig have signed along
+    /// with the [`ModeInfo`] for each of their signatures.
+    pub fn mode_info(&self) -> ModeInfo {
+        let mut bitarray = CompactBitArray::with_len(self.signatures.len());
+        let mut mode_infos = Vec::with_capacity(self.signature_count());
+
+        for (index, (mode_info, _)) in self.iter() {
+            bitarray.set(index, true);
+            mode_infos.push(mode_info.clone());
+        }
+
+        mode_info::Multi {
+            bitarray,
+            mode_infos,
+        }
+        .into()
+    }
+
+    /// Get the [`SignerInfo`] for this multisig signature.
+    pub fn signer_info(&self, sequence: SequenceNumber) -> SignerInfo {
+        SignerInfo {
+            public_key: Some(self.public_key.clone().into()),
+            mode_info: self.mode_info(),
+            sequence,
+        }
+    }
+
+    /// Combine the member signatures into a single signature, which is the
+    /// Protobuf encoding of a `MultiSignature` containing the member
+    /// signatures ordered by their position in the multisig.
+    ///
+    /// Returns an error if the multisig threshold has not been reached.
+    pub fn to_bytes(&self) -> Result<SignatureBytes> {
+        if !self.is_complete() {
+            return Err(Error::Crypto).wrap_err_with(|| {
+                format!(
+                    "multisig threshold not reached: {} of {} signatures",
+                    self.signature_count(),
+                    self.public_key.threshold
+                )
+            });
+        }
+
+        proto::cosmos::crypto::multisig::v1beta1::MultiSignature {
+            signatures: self.iter().map(|(_, (_, sig))| sig.clone()).collect(),
+        }
+        .to_bytes()
+    }
+
+    /// Set the signature at the given index.
+    fn set(&mut self, index: usize, mode_info: ModeInfo, signature: SignatureBytes) -> Result<()> {
+        let len = self.signatures.len();
+        let slot = self
+            .signatures
+            .get_mut(index)
+            .ok_or(Error::Crypto)
+            .wrap_err_with(|| {
+                format!("multisig member index out of range: {} >= {}", index, len)
+            })?;
+
+        *slot = Some((mode_info, signature));
+        Ok(())
+    }
+
+    /// Iterate over the signatures which are present along with their index.
+    fn iter(&self) -> impl Iterator<Item = (usize, &(ModeInfo, SignatureBytes))> {
+        self.signatures
+            .iter()
+            .enumerate()
+            .filter_map(|(index, sig)| sig.as_ref().map(|sig| (index, sig)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MultisigSignature;
+    use crate::{
+        crypto::{secp256k1, LegacyAminoMultisig},
+        proto,
+        tx::{ModeInfo, SignMode},
+    };
+    use prost::Message;
+
+    #[test]
+    fn combine_signatures() {
+        let signing_keys = (0..3)
+            .map(|_| secp256k1::SigningKey::random())
+            .collect::<Vec<_>>();
+
+        let multisig = LegacyAminoMultisig {
+            threshold: 2,
+            public_keys: signing_keys
+                .iter()
+                .map(|sk| sk.public_key().into())
+                .collect(),
+        };
+
+        let mut multisig_signature = MultisigSignature::new(multisig);
+        let sig0 = signing_keys[0]
+            .sign(b"sign bytes")
+            .unwrap()
+            .as_ref()
+            .to_vec();
+        let sig2 = signing_keys[2]
+            .sign(b"sign bytes")
+            .unwrap()
+            .as_ref()
+            .to_vec();
+
+        multisig_signature
+            .add_signature(&signing_keys[2].public_key(), sig2.clone())
+            .unwrap();
+        assert!(!multisig_signature.is_complete());
+        assert!(multisig_signature.to_bytes().is_err());
+        assert!(multisig_signature
+            .set_signature(3, SignMode::LegacyAminoJson, sig2.clone())
+            .is_err());
+
+        multisig_signature
+            .add_signature(&signing_keys[0].public_key(), sig0.clone())
+            .unwrap();
+        assert!(multisig_signature.is_complete());
+
+        match multisig_signature.mode_info() {
+            ModeInfo::Multi(multi) => {
+                assert_eq!(multi.bitarray.len(), 3);
+                assert!(multi.bitarray.get(0));
+                assert!(!multi.bitarray.get(1));
+                assert!(multi.bitarray.get(2));
+                assert_eq!(
+                    multi.mode_infos,
+                    [
+                        ModeInfo::single(SignMode::LegacyAminoJson),
+                        ModeInfo::single(SignMode::LegacyAminoJson)
+                    ]
+                );
+            }
+            other => panic!("unexpected mode info: {:?}", other),
+        }
+
+        let bytes = multisig_signature.to_bytes().unwrap();
+        let decoded =
+            proto::cosmos::crypto::multisig::v1beta1::MultiSignature::decode(&*bytes).unwrap();
+        assert_eq!(decoded.signatures, [sig0, sig2]);
+    }
+}
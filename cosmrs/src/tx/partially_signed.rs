@@ -0,0 +1,421 @@
+//! Partially signed transactions.
+
+use super::{AuthInfo, Body, ModeInfo, Raw, SignatureBytes, SignerData};
+use crate::{
+    crypto::{AsyncTxSigner, PublicKey, TxSigner},
+    proto, Error, Result,
+};
+use eyre::WrapErr;
+use prost::Message;
+
+/// [`PartiallySigned`] is a transaction with several signers, each of which
+/// signs it independently.
+///
+/// The transaction's [`Body`] and [`AuthInfo`] are fixed when it's created,
+/// after which each signer adds their signature at the index of their
+/// [`SignerInfo`][`super::SignerInfo`] in any order. Once all signatures are
+/// present, it can be converted into a [`Raw`] transaction.
+///
+/// It can be serialized with [`PartiallySigned::to_bytes`] in order to pass it
+/// between signers. The serialization is a `TxRaw` in which missing
+/// signatures are empty.
+#[derive(Clone, Debug)]
+pub struct PartiallySigned {
+    /// Transaction body.
+    body: Body,
+
+    /// Transaction auth info.
+    auth_info: AuthInfo,
+
+    /// Transaction without any signatures.
+    unsigned: Raw,
+
+    /// Signatures, which are empty if missing.
+    signatures: Vec<SignatureBytes>,
+}
+
+impl PartiallySigned {
+    /// Create a new [`PartiallySigned`] transaction with the given [`Body`]
+    /// and [`AuthInfo`], which initially has no signatures.
+    pub fn new(body: Body, auth_info: AuthInfo) -> Result<Self> {
+        let unsigned = proto::cosmos::tx::v1beta1::TxRaw {
+            body_bytes: body.clone().into_bytes()?,
+            auth_info_bytes: auth_info.clone().into_bytes()?,
+            signatures: Vec::new(),
+        };
+
+        Ok(Self {
+            signatures: vec![Vec::new(); auth_info.signer_infos.len()],
+            body,
+            auth_info,
+            unsigned: unsigned.into(),
+        })
+    }
+
+    /// Deserialize a [`PartiallySigned`] transaction from serialized protobuf.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut raw = proto::cosmos::tx::v1beta1::TxRaw::decode(bytes)?;
+        let body = proto::cosmos::tx::v1beta1::TxBody::decode(&*raw.body_bytes)?.try_into()?;
+        let auth_info = AuthInfo::try_from(proto::cosmos::tx::v1beta1::AuthInfo::decode(
+            &*raw.auth_info_bytes,
+        )?)?;
+
+        let signer_count = auth_info.signer_infos.len();
+        let mut signatures = std::mem::take(&mut raw.signatures);
+
+        if signatures.is_empty() {
+            signatures.resize(signer_count, Vec::new());
+        } else if signatures.len() != signer_count {
+            return Err(Error::Signature).wrap_err_with(|| {
+                format!(
+                    "expected {} signatures, got {}",
+                    signer_count,
+                    signatures.len()
+                )
+            });
+        }
+
+        Ok(Self {
+            body,
+            auth_info,
+            unsigned: raw.into(),
+            signatures,
+        })
+    }
+
+    /// Serialize this [`PartiallySigned`] transaction as a byte vector.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        self.to_raw().to_bytes()
+    }
+
+    /// Get the transaction [`Body`].
+    pub fn body(&self) -> &Body {
+        &self.body
+    }
+
+    /// Get the transaction [`AuthInfo`].
+    pub fn auth_info(&self) -> &AuthInfo {
+        &self.auth_info
+    }
+
+    /// Sign this transaction with the given signing key.
+    ///
+    /// The signature is added at the index of the [`SignerInfo`][`super::SignerInfo`]
+    /// whose public key matches the signing key, using its signing mode. The
+    /// sequence of the [`SignerData`] must match the sequence of the signer info.
+    ///
+    /// Signer infos may omit the public key when the signer's account already
+    /// has one on chain, in which case [`PartiallySigned::sign_at`] must be used.
+    pub fn sign<S: TxSigner + ?Sized>(
+        &mut self,
+        signing_key: &S,
+        signer_data: &SignerData,
+    ) -> Result<()> {
+        let public_key = signing_key.public_key();
+        let index = self.signer_index(&public_key)?;
+        self.sign_at(index, signing_key, signer_data)
+    }
+
+    /// Sign this transaction with the given signing key as the signer at the
+    /// given index.
+    ///
+    /// If the signer info at the given index includes a public key, it must
+    /// match the signing key. See [`PartiallySigned::sign`] for how the
+    /// signature is added.
+    pub fn sign_at<S: TxSigner + ?Sized>(
+        &mut self,
+        index: usize,
+        signing_key: &S,
+        signer_data: &SignerData,
+    ) -> Result<()> {
+        let sign_bytes = self.sign_bytes_at(index, &signing_key.public_key(), signer_data)?;
+        let signature = signing_key.sign_tx(&sign_bytes)?;
+        self.add_signature(index, signature)
+    }
+
+    /// Sign this transaction with the given [`AsyncTxSigner`], e.g. a remote
+    /// signer.
+    ///
+    /// See [`PartiallySigned::sign`] for how the signature is added.
+    pub async fn sign_async<S: AsyncTxSigner + ?Sized>(
+        &mut self,
+        signer: &S,
+        signer_data: &SignerData,
+    ) -> Result<()> {
+        let index = self.signer_index(&signer.signer_public_key())?;
+        self.sign_async_at(index, signer, signer_data).await
+    }
+
+    /// Sign this transaction with the given [`AsyncTxSigner`] as the signer
+    /// at the given index.
+    ///
+    /// See [`PartiallySigned::sign_at`] for how the signer is checked.
+    pub async fn sign_async_at<S: AsyncTxSigner + ?Sized>(
+        &mut self,
+        index: usize,
+        signer: &S,
+        signer_data: &SignerData,
+    ) -> Result<()> {
+        let sign_bytes = self.sign_bytes_at(index, &signer.signer_public_key(), signer_data)?;
+        let signature = signer.sign_tx_async(&sign_bytes).await?;
+        self.add_signature(index, signature)
+    }
+
+    /// Add a signature for the signer at the given index.
+    pub fn add_signature(&mut self, index: usize, signature: SignatureBytes) -> Result<()> {
+        if signature.is_empty() {
+            return Err(Error::Signature).wrap_err("signature is empty");
+        }
+
+        let signer_count = self.signatures.len();
+
+        *self
+            .signatures
+            .get_mut(index)
+            .ok_or(Error::Signature)
+            .wrap_err_with(|| {
+                format!("signer index out of range: {} >= {}", index, signer_count)
+            })? = signature;
+
+        Ok(())
+    }
+
+    /// Get the indexes of the signers which have yet to sign this transaction.
+    pub fn missing_signers(&self) -> Vec<usize> {
+        self.signatures
+            .iter()
+            .enumerate()
+            .filter(|(_, signature)| signature.is_empty())
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Have all signers signed this transaction?
+    pub fn is_complete(&self) -> bool {
+        self.missing_signers().is_empty()
+    }
+
+    /// Convert this transaction into a [`Raw`] transaction.
+    ///
+    /// Returns an error if any signatures are missing.
+    pub fn into_raw(self) -> Result<Raw> {
+        let missing_signers = self.missing_signers();
+
+        if !missing_signers.is_empty() {
+            return Err(Error::Signature).wrap_err_with(|| {
+                format!("missing signatures for signers: {:?}", missing_signers)
+            });
+        }
+
+        Ok(self.to_raw())
+    }
+
+    /// Find the index of the signer with the given public key.
+    fn signer_index(&self, public_key: &PublicKey) -> Result<usize> {
+        self.auth_info
+            .signer_infos
+            .iter()
+            .position(|signer_info| {
+                signer_info.public_key.as_ref().and_then(|pk| pk.single()) == Some(public_key)
+            })
+            .ok_or(Error::Crypto)
+            .wrap_err("signing key doesn't match any of the transaction's signers")
+    }
+
+    /// Compute the sign bytes for the signer at the given index, checking the
+    /// public key of its signer info (if present) matches the given one.
+    fn sign_bytes_at(
+        &self,
+        index: usize,
+        public_key: &PublicKey,
+        signer_data: &SignerData,
+    ) -> Result<Vec<u8>> {
+        let signer_count = self.auth_info.signer_infos.len();
+        let signer_info = self
+            .auth_info
+            .signer_infos
+            .get(index)
+            .ok_or(Error::Signature)
+            .wrap_err_with(|| {
+                format!("signer index out of range: {} >= {}", index, signer_count)
+            })?;
+
+        if let Some(signer_public_key) = &signer_info.public_key {
+            if signer_public_key.single() != Some(public_key) {
+                return Err(Error::Crypto).wrap_err_with(|| {
+                    format!(
+                        "signing key doesn't match the public key of signer {}",
+                        index
+                    )
+                });
+            }
+        }
+
+        if signer_info.sequence != signer_data.sequence {
+            return Err(Error::Signature).wrap_err_with(|| {
+                format!(
+                    "sequence mismatch for signer {}: expected {}, got {}",
+                    index, signer_info.sequence, signer_data.sequence
+                )
+            });
+        }
+
+        let sign_mode = match &signer_info.mode_info {
+            ModeInfo::Single(single) => single.mode,
+            ModeInfo::Multi(_) => {
+                return Err(Error::Signature).wrap_err_with(|| {
+                    format!(
+                        "signer {} is a multisig and can't be signed directly",
+                        index
+                    )
+                })
+            }
+        };
+
+        self.unsigned
+            .sign_bytes(&self.body, &self.auth_info.fee, sign_mode, signer_data)
+    }
+
+    /// Get a [`Raw`] transaction containing the signatures which are present.
+    fn to_raw(&self) -> Raw {
+        let mut raw = proto::cosmos::tx::v1beta1::TxRaw::from(self.unsigned.clone());
+        raw.signatures = self.signatures.clone();
+        raw.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PartiallySigned;
+    use crate::{
+        bank::MsgSend,
+        crypto::secp256k1,
+        tx::{AuthInfo, Body, Fee, Msg, SignDoc, SignerData, SignerInfo},
+        Coin,
+    };
+
+    #[test]
+    fn sign_with_fee_payer() {
+        let sender = secp256k1::SigningKey::random();
+        let payer = secp256k1::SigningKey::random();
+        let chain_id = "cosmoshub-4".parse::<tendermint::chain::Id>().unwrap();
+
+        let msg_send = MsgSend {
+            from_address: sender.public_key().account_id("cosmos").unwrap(),
+            to_address: "cosmos19dyl0uyzes4k23lscla02n06fc22h4uqsdwq6z"
+                .parse()
+                .unwrap(),
+            amount: vec![Coin {
+                denom: "uatom".parse().unwrap(),
+                amount: 1_000_000u64.into(),
+            }],
+        };
+
+        let mut fee = Fee::from_amount_and_gas(
+            Coin {
+                denom: "uatom".parse().unwrap(),
+                amount: 5_000u64.into(),
+            },
+            200_000u64,
+        );
+        fee.payer = Some(payer.public_key().account_id("cosmos").unwrap());
+
+        let body = Body::new(vec![msg_send.to_any().unwrap()], "memo", 0u16);
+        let auth_info = AuthInfo {
+            signer_infos: vec![
+                SignerInfo::single_direct(Some(sender.public_key()), 5),
+                SignerInfo::single_amino_json(Some(payer.public_key()), 0),
+            ],
+            fee,
+        };
+
+        let mut tx = PartiallySigned::new(body, auth_info).unwrap();
+        assert_eq!(tx.missing_signers(), [0, 1]);
+
+        let payer_data = SignerData {
+            chain_id: chain_id.clone(),
+            account_number: 9,
+            sequence: 0,
+        };
+        tx.sign(&payer, &payer_data).unwrap();
+        assert_eq!(tx.missing_signers(), [0]);
+
+        // Pass the transaction to the sender
+        let mut tx = PartiallySigned::from_bytes(&tx.to_bytes().unwrap()).unwrap();
+        assert!(tx.clone().into_raw().is_err());
+
+        let sender_data = SignerData {
+            chain_id: chain_id.clone(),
+            account_number: 1,
+            sequence: 4,
+        };
+        assert!(tx.sign(&sender, &sender_data).is_err());
+
+        let sender_data = SignerData {
+            sequence: 5,
+            ..sender_data
+        };
+        tx.sign(&sender, &sender_data).unwrap();
+        assert!(tx.is_complete());
+
+        let raw = tx.into_raw().unwrap();
+        raw.verify_signatures(&chain_id, &[1, 9]).unwrap();
+    }
+
+    #[test]
+    fn sign_at_index_without_public_key() {
+        let sender = secp256k1::SigningKey::random();
+        let other = secp256k1::SigningKey::random();
+        let chain_id = "cosmoshub-4".parse::<tendermint::chain::Id>().unwrap();
+
+        let msg_send = MsgSend {
+            from_address: sender.public_key().account_id("cosmos").unwrap(),
+            to_address: "cosmos19dyl0uyzes4k23lscla02n06fc22h4uqsdwq6z"
+                .parse()
+                .unwrap(),
+            amount: vec![],
+        };
+
+        let fee = Fee::from_amount_and_gas(
+            Coin {
+                denom: "uatom".parse().unwrap(),
+                amount: 5_000u64.into(),
+            },
+            200_000u64,
+        );
+
+        let body = Body::new(vec![msg_send.to_any().unwrap()], "memo", 0u16);
+        let auth_info = AuthInfo {
+            signer_infos: vec![
+                SignerInfo::single_direct(None, 3),
+                SignerInfo::single_direct(Some(other.public_key()), 0),
+            ],
+            fee,
+        };
+
+        let mut tx = PartiallySigned::new(body.clone(), auth_info.clone()).unwrap();
+        let sender_data = SignerData {
+            chain_id: chain_id.clone(),
+            account_number: 1,
+            sequence: 3,
+        };
+
+        // The sender's signer info has no public key to match against
+        assert!(tx.sign(&sender, &sender_data).is_err());
+        assert!(tx.sign_at(1, &sender, &sender_data).is_err());
+        assert!(tx.sign_at(2, &sender, &sender_data).is_err());
+
+        tx.sign_at(0, &sender, &sender_data).unwrap();
+        assert_eq!(tx.missing_signers(), [1]);
+
+        let sign_bytes = SignDoc::new(&body, &auth_info, &chain_id, 1)
+            .unwrap()
+            .into_bytes()
+            .unwrap();
+        let signatures = crate::proto::cosmos::tx::v1beta1::TxRaw::from(tx.to_raw()).signatures;
+        sender
+            .public_key()
+            .verify(&sign_bytes, &signatures[0])
+            .unwrap();
+    }
+}
@@ -1,10 +1,25 @@
 //! Raw transaction.
 
-use crate::{prost_ext::MessageExt, proto, Result};
+use super::{
+    mode_info, AccountNumber, AuthInfo, Body, Fee, Hash, ModeInfo, SignDoc, SignMode, SignerData,
+    SignerPublicKey, StdSignDoc,
+};
+use crate::{crypto::LegacyAminoMultisig, prost_ext::MessageExt, proto, Error, Result};
+use eyre::WrapErr;
+use prost::Message;
+use sha2::{Digest, Sha256};
+use tendermint::chain;
+
+#[cfg(feature = "grpc")]
+use {super::GasInfo, tonic::transport::Channel};
 
 #[cfg(feature = "rpc")]
 use crate::rpc;
 
+/// Client for the transaction gRPC service.
+#[cfg(feature = "grpc")]
+pub type ServiceClient = proto::cosmos::tx::v1beta1::service_client::ServiceClient<Channel>;
+
 /// Response from `/broadcast_tx_commit`
 #[cfg(feature = "rpc")]
 pub type TxCommitResponse = rpc::endpoint::broadcast::tx_commit::Response;
@@ -24,6 +39,133 @@ impl Raw {
         self.0.to_bytes()
     }
 
+    /// Compute the transaction hash, which is the SHA-256 digest of the
+    /// serialized transaction.
+    ///
+    /// This is the hash used to identify the transaction once it has been
+    /// broadcast, e.g. with [`Tx::find_by_hash`][`super::Tx::find_by_hash`].
+    pub fn hash(&self) -> Result<Hash> {
+        Ok(Hash::new(Sha256::digest(&self.to_bytes()?).into()))
+    }
+
+    /// Verify the signatures of this transaction.
+    ///
+    /// The sign bytes for each signer are recomputed from the transaction using
+    /// the given chain ID and the signer's account number, which must be provided
+    /// in the same order as the transaction's signer infos.
+    ///
+    /// Every signer info must include the signer's public key. Supported key types
+    /// are those of [`PublicKey`][`crate::crypto::PublicKey`] and
+    /// [`LegacyAminoMultisig`], signed using either `SIGN_MODE_DIRECT` or
+    /// `SIGN_MODE_LEGACY_AMINO_JSON`.
+    pub fn verify_signatures(
+        &self,
+        chain_id: &chain::Id,
+        account_numbers: &[AccountNumber],
+    ) -> Result<()> {
+        let body = Body::try_from(proto::cosmos::tx::v1beta1::TxBody::decode(
+            &*self.0.body_bytes,
+        )?)?;
+        let auth_info = AuthInfo::try_from(proto::cosmos::tx::v1beta1::AuthInfo::decode(
+            &*self.0.auth_info_bytes,
+        )?)?;
+
+        let signer_count = auth_info.signer_infos.len();
+
+        if self.0.signatures.len() != signer_count || account_numbers.len() != signer_count {
+            return Err(Error::Signature).wrap_err_with(|| {
+                format!(
+                    "expected {} signatures and account numbers, got {} and {}",
+                    signer_count,
+                    self.0.signatures.len(),
+                    account_numbers.len()
+                )
+            });
+        }
+
+        for (i, ((signer_info, signature), &account_number)) in auth_info
+            .signer_infos
+            .iter()
+            .zip(&self.0.signatures)
+            .zip(account_numbers)
+            .enumerate()
+        {
+            let signer_data = SignerData {
+                chain_id: chain_id.clone(),
+                account_number,
+                sequence: signer_info.sequence,
+            };
+
+            let sign_bytes =
+                |sign_mode| self.sign_bytes(&body, &auth_info.fee, sign_mode, &signer_data);
+
+            signer_info
+                .public_key
+                .as_ref()
+                .ok_or(Error::MissingField { name: "public_key" })
+                .map_err(Into::into)
+                .and_then(|public_key| {
+                    verify_signature(public_key, &signer_info.mode_info, signature, &sign_bytes)
+                })
+                .wrap_err_with(|| format!("signature verification failed for signer {}", i))?;
+        }
+
+        Ok(())
+    }
+
+    /// Compute the sign bytes for a signer of this transaction using the
+    /// given [`SignMode`].
+    ///
+    /// The [`Body`] and [`Fee`] must be the decoded body and fee of this transaction.
+    pub(crate) fn sign_bytes(
+        &self,
+        body: &Body,
+        fee: &Fee,
+        sign_mode: SignMode,
+        signer_data: &SignerData,
+    ) -> Result<Vec<u8>> {
+        match sign_mode {
+            SignMode::Direct => SignDoc {
+                body_bytes: self.0.body_bytes.clone(),
+                auth_info_bytes: self.0.auth_info_bytes.clone(),
+                chain_id: signer_data.chain_id.to_string(),
+                account_number: signer_data.account_number,
+            }
+            .into_bytes(),
+            SignMode::LegacyAminoJson => StdSignDoc::new(
+                body,
+                fee,
+                &signer_data.chain_id,
+                signer_data.account_number,
+                signer_data.sequence,
+            )?
+            .to_bytes(),
+            _ => Err(Error::SignMode { sign_mode }.into()),
+        }
+    }
+
+    /// Simulate this transaction using the provided gRPC client, returning
+    /// the [`GasInfo`] for its execution.
+    ///
+    /// Signatures aren't verified when simulating a transaction, so they may
+    /// be empty. However, signer infos must include public keys.
+    #[cfg(feature = "grpc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "grpc")))]
+    pub async fn simulate(&self, client: &mut ServiceClient) -> Result<GasInfo> {
+        #[allow(deprecated)]
+        let request = proto::cosmos::tx::v1beta1::SimulateRequest {
+            tx: None,
+            tx_bytes: self.to_bytes()?,
+        };
+
+        let response = client.simulate(request).await?.into_inner();
+
+        Ok(response
+            .gas_info
+            .ok_or(Error::MissingField { name: "gas_info" })?
+            .into())
+    }
+
     /// Broadcast this transaction using the provided RPC client
     #[cfg(feature = "rpc")]
     #[cfg_attr(docsrs, doc(cfg(feature = "rpc")))]
@@ -35,6 +177,84 @@ impl Raw {
     }
 }
 
+/// Verify a signature by the given public key using the given [`ModeInfo`],
+/// where `sign_bytes` computes the sign bytes for a given [`SignMode`].
+fn verify_signature(
+    public_key: &SignerPublicKey,
+    mode_info: &ModeInfo,
+    signature: &[u8],
+    sign_bytes: &dyn Fn(SignMode) -> Result<Vec<u8>>,
+) -> Result<()> {
+    match (public_key, mode_info) {
+        (SignerPublicKey::Single(public_key), ModeInfo::Single(single)) => {
+            public_key.verify(&sign_bytes(single.mode)?, signature)
+        }
+        (SignerPublicKey::LegacyAminoMultisig(multisig), ModeInfo::Multi(multi)) => {
+            verify_multisig(multisig, multi, signature, sign_bytes)
+        }
+        (SignerPublicKey::Any(any), _) => Err(Error::Crypto)
+            .wrap_err_with(|| format!("unsupported public key type: {}", any.type_url)),
+        _ => Err(Error::Signature).wrap_err("mode info doesn't match public key type"),
+    }
+}
+
+/// Verify a [`LegacyAminoMultisig`] signature.
+fn verify_multisig(
+    multisig: &LegacyAminoMultisig,
+    multi: &mode_info::Multi,
+    signature: &[u8],
+    sign_bytes: &dyn Fn(SignMode) -> Result<Vec<u8>>,
+) -> Result<()> {
+    let signatures =
+        proto::cosmos::crypto::multisig::v1beta1::MultiSignature::decode(signature)?.signatures;
+
+    if multi.bitarray.len() != multisig.public_keys.len() {
+        return Err(Error::Signature).wrap_err_with(|| {
+            format!(
+                "bit array size is incorrect: expected {}, got {}",
+                multisig.public_keys.len(),
+                multi.bitarray.len()
+            )
+        });
+    }
+
+    let signer_count = multi.bitarray.count_ones();
+
+    if signatures.len() != signer_count || multi.mode_infos.len() != signer_count {
+        return Err(Error::Signature).wrap_err_with(|| {
+            format!(
+                "expected {} signatures and mode infos, got {} and {}",
+                signer_count,
+                signatures.len(),
+                multi.mode_infos.len()
+            )
+        });
+    }
+
+    if signer_count < multisig.threshold as usize {
+        return Err(Error::Signature).wrap_err_with(|| {
+            format!(
+                "multisig threshold not reached: {} of {} signatures",
+                signer_count, multisig.threshold
+            )
+        });
+    }
+
+    let signers = multisig
+        .public_keys
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| multi.bitarray.get(*i))
+        .map(|(_, public_key)| public_key);
+
+    // Nested multisigs are verified recursively
+    for ((public_key, mode_info), signature) in signers.zip(&multi.mode_infos).zip(&signatures) {
+        verify_signature(public_key, mode_info, signature, sign_bytes)?;
+    }
+
+    Ok(())
+}
+
 impl From<proto::cosmos::tx::v1beta1::TxRaw> for Raw {
     fn from(tx: proto::cosmos::tx::v1beta1::TxRaw) -> Self {
         Raw(tx)
@@ -46,3 +266,259 @@ impl From<Raw> for proto::cosmos::tx::v1beta1::TxRaw {
         tx.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Raw;
+    use crate::{
+        bank::MsgSend,
+        crypto::{ed25519, eth_secp256k1, secp256k1, secp256r1, LegacyAminoMultisig, TxSigner},
+        proto,
+        tx::{
+            Body, Fee, ModeInfo, Msg, MultisigSignature, SignDoc, SignMode, SignerInfo,
+            SignerPublicKey, StdSignDoc, Tx,
+        },
+        Coin,
+    };
+
+    fn body<S: TxSigner>(from_address: &S) -> Body {
+        let msg_send = MsgSend {
+            from_address: from_address.public_key().account_id("cosmos").unwrap(),
+            to_address: "cosmos19dyl0uyzes4k23lscla02n06fc22h4uqsdwq6z"
+                .parse()
+                .unwrap(),
+            amount: vec![Coin {
+                denom: "uatom".parse().unwrap(),
+                amount: 1_000_000u64.into(),
+            }],
+        };
+
+        Body::new(vec![msg_send.to_any().unwrap()], "memo", 0u16)
+    }
+
+    fn fee() -> Fee {
+        Fee::from_amount_and_gas(
+            Coin {
+                denom: "uatom".parse().unwrap(),
+                amount: 5_000u64.into(),
+            },
+            200_000u64,
+        )
+    }
+
+    #[test]
+    fn hash() {
+        let raw = Raw::from(proto::cosmos::tx::v1beta1::TxRaw {
+            body_bytes: b"body".to_vec(),
+            auth_info_bytes: b"auth info".to_vec(),
+            signatures: vec![b"signature".to_vec()],
+        });
+
+        // SHA-256 of `0a04626f647912096175746820696e666f1a097369676e6174757265`
+        assert_eq!(
+            raw.hash().unwrap().to_string(),
+            "305C2C57FD69A4557DB3FDBA95303869CE01E4505115B7C590A4842746F88820"
+        );
+    }
+
+    #[test]
+    fn verify_direct() {
+        let signing_key = secp256k1::SigningKey::random();
+        let chain_id = "cosmoshub-4".parse().unwrap();
+        let auth_info =
+            SignerInfo::single_direct(Some(signing_key.public_key()), 7).auth_info(fee());
+        let sign_doc = SignDoc::new(&body(&signing_key), &auth_info, &chain_id, 1).unwrap();
+        let raw = sign_doc.sign(&signing_key).unwrap();
+
+        raw.verify_signatures(&chain_id, &[1]).unwrap();
+        assert!(raw.verify_signatures(&chain_id, &[2]).is_err());
+        assert!(raw.verify_signatures(&chain_id, &[]).is_err());
+        assert!(raw
+            .verify_signatures(&"cosmoshub-3".parse().unwrap(), &[1])
+            .is_err());
+    }
+
+    #[test]
+    fn verify_direct_ed25519() {
+        let signing_key = ed25519::SigningKey::random();
+        let chain_id = "cosmoshub-4".parse().unwrap();
+        let auth_info =
+            SignerInfo::single_direct(Some(signing_key.public_key()), 7).auth_info(fee());
+        let sign_doc = SignDoc::new(&body(&signing_key), &auth_info, &chain_id, 1).unwrap();
+        let raw = sign_doc.sign(&signing_key).unwrap();
+
+        raw.verify_signatures(&chain_id, &[1]).unwrap();
+        assert!(raw.verify_signatures(&chain_id, &[2]).is_err());
+    }
+
+    #[test]
+    fn verify_direct_eth_secp256k1() {
+        let signing_key = eth_secp256k1::SigningKey::random();
+        let chain_id = "evmos_9001-2".parse().unwrap();
+        let auth_info =
+            SignerInfo::single_direct(Some(signing_key.public_key()), 7).auth_info(fee());
+        let sign_doc = SignDoc::new(&body(&signing_key), &auth_info, &chain_id, 1).unwrap();
+        let raw = sign_doc.sign(&signing_key).unwrap();
+
+        raw.verify_signatures(&chain_id, &[1]).unwrap();
+        assert!(raw.verify_signatures(&chain_id, &[2]).is_err());
+
+        // Round trip through the Protobuf binary and JSON encodings
+        let decoded = Raw::from_bytes(&raw.to_bytes().unwrap()).unwrap();
+        decoded.verify_signatures(&chain_id, &[1]).unwrap();
+
+        let tx = Tx::from_bytes(&decoded.to_bytes().unwrap()).unwrap();
+        assert_eq!(
+            tx.auth_info.signer_infos[0].public_key,
+            Some(SignerPublicKey::Single(signing_key.public_key()))
+        );
+        let from_json = Tx::from_json(&tx.to_json().unwrap()).unwrap();
+        from_json
+            .into_raw()
+            .unwrap()
+            .verify_signatures(&chain_id, &[1])
+            .unwrap();
+    }
+
+    #[test]
+    fn verify_direct_secp256r1() {
+        let signing_key = secp256r1::SigningKey::random();
+        let chain_id = "cosmoshub-4".parse().unwrap();
+        let auth_info =
+            SignerInfo::single_direct(Some(signing_key.public_key()), 7).auth_info(fee());
+        let sign_doc = SignDoc::new(&body(&signing_key), &auth_info, &chain_id, 1).unwrap();
+        let raw = sign_doc.sign(&signing_key).unwrap();
+
+        raw.verify_signatures(&chain_id, &[1]).unwrap();
+        assert!(raw.verify_signatures(&chain_id, &[2]).is_err());
+
+        // Round trip through the Protobuf binary and JSON encodings
+        let decoded = Raw::from_bytes(&raw.to_bytes().unwrap()).unwrap();
+        decoded.verify_signatures(&chain_id, &[1]).unwrap();
+
+        let tx = Tx::from_bytes(&decoded.to_bytes().unwrap()).unwrap();
+        assert_eq!(
+            tx.auth_info.signer_infos[0].public_key,
+            Some(SignerPublicKey::Single(signing_key.public_key()))
+        );
+        let from_json = Tx::from_json(&tx.to_json().unwrap()).unwrap();
+        from_json
+            .into_raw()
+            .unwrap()
+            .verify_signatures(&chain_id, &[1])
+            .unwrap();
+    }
+
+    #[test]
+    fn verify_amino_multisig() {
+        let signing_keys = (0..3)
+            .map(|_| secp256k1::SigningKey::random())
+            .collect::<Vec<_>>();
+
+        let multisig = LegacyAminoMultisig {
+            threshold: 2,
+            public_keys: signing_keys
+                .iter()
+                .map(|sk| sk.public_key().into())
+                .collect(),
+        };
+
+        let chain_id = "cosmoshub-4".parse().unwrap();
+        let body = body(&signing_keys[0]);
+        let sign_doc = StdSignDoc::new(&body, &fee(), &chain_id, 3, 0).unwrap();
+        let mut multisig_signature = MultisigSignature::new(multisig);
+
+        for signing_key in &signing_keys[1..] {
+            multisig_signature
+                .add_signature(
+                    &signing_key.public_key(),
+                    sign_doc.sign(signing_key).unwrap(),
+                )
+                .unwrap();
+        }
+
+        let tx = Tx {
+            body,
+            auth_info: multisig_signature.signer_info(0).auth_info(fee()),
+            signatures: vec![multisig_signature.to_bytes().unwrap()],
+        };
+
+        let raw = tx.into_raw().unwrap();
+        raw.verify_signatures(&chain_id, &[3]).unwrap();
+        assert!(raw.verify_signatures(&chain_id, &[4]).is_err());
+    }
+
+    #[test]
+    fn verify_nested_amino_multisig() {
+        let signing_keys = (0..4)
+            .map(|_| secp256k1::SigningKey::random())
+            .collect::<Vec<_>>();
+        let public_keys = signing_keys
+            .iter()
+            .map(|sk| sk.public_key())
+            .collect::<Vec<_>>();
+
+        // 2-of-[1-of-[key 0, key 1], key 2, key 3]
+        let inner = LegacyAminoMultisig::new(1, public_keys[..2].to_vec()).unwrap();
+        let outer = LegacyAminoMultisig::new(
+            2,
+            vec![
+                SignerPublicKey::from(inner.clone()),
+                public_keys[2].into(),
+                public_keys[3].into(),
+            ],
+        )
+        .unwrap();
+
+        let chain_id = "cosmoshub-4".parse().unwrap();
+        let body = body(&signing_keys[0]);
+        let sign_doc = StdSignDoc::new(&body, &fee(), &chain_id, 3, 0).unwrap();
+
+        let mut inner_signature = MultisigSignature::new(inner);
+        inner_signature
+            .add_signature(&public_keys[1], sign_doc.sign(&signing_keys[1]).unwrap())
+            .unwrap();
+
+        let mut outer_signature = MultisigSignature::new(outer);
+        assert!(outer_signature
+            .add_signature(&public_keys[1], sign_doc.sign(&signing_keys[1]).unwrap())
+            .is_err());
+        assert!(outer_signature
+            .set_signature(0, SignMode::LegacyAminoJson, vec![0; 64])
+            .is_err());
+        assert!(outer_signature.set_nested(1, &inner_signature).is_err());
+
+        outer_signature.set_nested(0, &inner_signature).unwrap();
+        outer_signature
+            .add_signature(&public_keys[3], sign_doc.sign(&signing_keys[3]).unwrap())
+            .unwrap();
+
+        let signer_info = outer_signature.signer_info(0);
+        match &signer_info.mode_info {
+            ModeInfo::Multi(multi) => {
+                assert_eq!(multi.mode_infos[0], inner_signature.mode_info());
+                assert!(matches!(multi.mode_infos[0], ModeInfo::Multi(_)));
+            }
+            other => panic!("unexpected mode info: {:?}", other),
+        }
+
+        let tx = Tx {
+            body,
+            auth_info: signer_info.clone().auth_info(fee()),
+            signatures: vec![outer_signature.to_bytes().unwrap()],
+        };
+
+        let raw = tx.into_raw().unwrap();
+        raw.verify_signatures(&chain_id, &[3]).unwrap();
+        assert!(raw.verify_signatures(&chain_id, &[4]).is_err());
+
+        // Round trip through the Protobuf binary and JSON encodings
+        let decoded = Tx::from_bytes(&raw.to_bytes().unwrap()).unwrap();
+        assert_eq!(
+            decoded.auth_info.signer_infos[0].public_key,
+            signer_info.public_key
+        );
+        let from_json = Tx::from_json(&decoded.to_json().unwrap()).unwrap();
+        assert_eq!(from_json.auth_info, decoded.auth_info);
+    }
+}
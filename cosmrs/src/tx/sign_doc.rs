@@ -1,7 +1,11 @@
 //! Signing document.
 
 use super::{AccountNumber, AuthInfo, Body, Raw};
-use crate::{crypto::secp256k1, prost_ext::MessageExt, proto, Result};
+use crate::{
+    crypto::{AsyncTxSigner, TxSigner},
+    prost_ext::MessageExt,
+    proto, Result,
+};
 use tendermint::chain;
 
 /// [`SignDoc`] is the type used for generating sign bytes for `SIGN_MODE_DIRECT`.
@@ -52,16 +56,30 @@ impl SignDoc {
         self.into_proto().to_bytes()
     }
 
-    /// Sign this [`SignDoc`], producing a [`Raw`] transaction.
-    pub fn sign(self, signing_key: &secp256k1::SigningKey) -> Result<Raw> {
+    /// Sign this [`SignDoc`] with any [`TxSigner`], producing a [`Raw`] transaction.
+    pub fn sign<S: TxSigner + ?Sized>(self, signing_key: &S) -> Result<Raw> {
         // TODO(tarcieri): optimize away `Clone` calls with reference conversions
         let sign_doc_bytes = self.clone().into_bytes()?;
-        let signature = signing_key.sign(&sign_doc_bytes)?;
+        let signature = signing_key.sign_tx(&sign_doc_bytes)?;
 
         Ok(proto::cosmos::tx::v1beta1::TxRaw {
             body_bytes: self.body_bytes,
             auth_info_bytes: self.auth_info_bytes,
-            signatures: vec![signature.as_ref().to_vec()],
+            signatures: vec![signature],
+        }
+        .into())
+    }
+
+    /// Sign this [`SignDoc`] with an [`AsyncTxSigner`], e.g. a remote signer,
+    /// producing a [`Raw`] transaction.
+    pub async fn sign_async<S: AsyncTxSigner + ?Sized>(self, signer: &S) -> Result<Raw> {
+        let sign_doc_bytes = self.clone().into_bytes()?;
+        let signature = signer.sign_tx_async(&sign_doc_bytes).await?;
+
+        Ok(proto::cosmos::tx::v1beta1::TxRaw {
+            body_bytes: self.body_bytes,
+            auth_info_bytes: self.auth_info_bytes,
+            signatures: vec![signature],
         }
         .into())
     }
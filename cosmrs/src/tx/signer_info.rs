@@ -38,6 +38,18 @@ impl SignerInfo {
         }
     }
 
+    /// Create [`SignerInfo`] for a single signer using `SIGN_MODE_LEGACY_AMINO_JSON`.
+    pub fn single_amino_json(
+        public_key: Option<PublicKey>,
+        sequence: SequenceNumber,
+    ) -> SignerInfo {
+        SignerInfo {
+            public_key: public_key.map(Into::into),
+            mode_info: ModeInfo::single(SignMode::LegacyAminoJson),
+            sequence,
+        }
+    }
+
     /// Get the [`AuthInfo`] for this signer with the given fee.
     ///
     /// This is primarily useful for cases involving a single signer.
@@ -143,9 +155,10 @@ impl TryFrom<Any> for SignerPublicKey {
 
     fn try_from(any: Any) -> Result<Self> {
         match any.type_url.as_str() {
-            PublicKey::ED25519_TYPE_URL | PublicKey::SECP256K1_TYPE_URL => {
-                PublicKey::try_from(any).map(Into::into)
-            }
+            PublicKey::ED25519_TYPE_URL
+            | PublicKey::SECP256K1_TYPE_URL
+            | PublicKey::SECP256R1_TYPE_URL
+            | PublicKey::ETH_SECP256K1_TYPE_URL => PublicKey::try_from(any).map(Into::into),
             LegacyAminoMultisig::TYPE_URL => LegacyAminoMultisig::try_from(any).map(Into::into),
             _ => Ok(Self::Any(any)),
         }
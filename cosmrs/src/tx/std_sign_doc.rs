@@ -0,0 +1,223 @@
+//! Legacy Amino JSON signing document.
+
+use super::{AccountNumber, AnyMsg, Body, Fee, SequenceNumber, SignMode, SignatureBytes};
+use crate::{
+    crypto::{AsyncTxSigner, TxSigner},
+    Any, Error, Result,
+};
+use eyre::WrapErr;
+use serde_json::{json, Value};
+use tendermint::{block, chain};
+
+/// [`StdSignDoc`] is the type used for generating sign bytes for
+/// `SIGN_MODE_LEGACY_AMINO_JSON`.
+///
+/// The sign bytes are the canonical (i.e. key-sorted) Amino JSON serialization
+/// of this document, which is computed by [`StdSignDoc::to_bytes`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct StdSignDoc {
+    /// `account_number` is the account number of the account in state.
+    pub account_number: AccountNumber,
+
+    /// `chain_id` is the unique identifier of the chain this transaction targets.
+    pub chain_id: String,
+
+    /// [`Fee`] paid by this transaction.
+    pub fee: Fee,
+
+    /// `memo` is any arbitrary memo to be added to the transaction.
+    pub memo: String,
+
+    /// Messages serialized as Amino JSON (see
+    /// [`LegacyAminoMsg::to_amino_json`][`super::LegacyAminoMsg::to_amino_json`]).
+    pub msgs: Vec<Value>,
+
+    /// Sequence of the signing account.
+    pub sequence: SequenceNumber,
+
+    /// `timeout_height` is the block height after which this transaction will
+    /// not be processed by the chain.
+    pub timeout_height: block::Height,
+}
+
+impl StdSignDoc {
+    /// Create a new [`StdSignDoc`] from a given transaction [`Body`] and [`Fee`].
+    ///
+    /// Every message in the [`Body`] must be of a type which impls
+    /// [`LegacyAminoMsg`][`super::LegacyAminoMsg`], and the [`Body`] must not
+    /// contain any extension options, as they can't be represented in Amino JSON.
+    pub fn new(
+        body: &Body,
+        fee: &Fee,
+        chain_id: &chain::Id,
+        account_number: AccountNumber,
+        sequence: SequenceNumber,
+    ) -> Result<Self> {
+        if !body.extension_options.is_empty() || !body.non_critical_extension_options.is_empty() {
+            return Err(Error::SignMode {
+                sign_mode: SignMode::LegacyAminoJson,
+            })
+            .wrap_err("extension options can't be signed using Amino JSON");
+        }
+
+        Ok(Self {
+            account_number,
+            chain_id: chain_id.to_string(),
+            fee: fee.clone(),
+            memo: body.memo.clone(),
+            msgs: body
+                .messages
+                .iter()
+                .map(msg_to_amino_json)
+                .collect::<Result<_, _>>()?,
+            sequence,
+            timeout_height: body.timeout_height,
+        })
+    }
+
+    /// Serialize this [`StdSignDoc`] as Amino JSON.
+    ///
+    /// Note that the keys of the resulting JSON object are not sorted: use
+    /// [`StdSignDoc::to_bytes`] to compute the sign bytes.
+    pub fn to_json(&self) -> Value {
+        let mut fee = json!({
+            "amount": self.fee.amount,
+            "gas": self.fee.gas_limit.value().to_string(),
+        });
+
+        if let Some(payer) = &self.fee.payer {
+            fee["payer"] = json!(payer);
+        }
+
+        if let Some(granter) = &self.fee.granter {
+            fee["granter"] = json!(granter);
+        }
+
+        let mut doc = json!({
+            "account_number": self.account_number.to_string(),
+            "chain_id": self.chain_id,
+            "fee": fee,
+            "memo": self.memo,
+            "msgs": self.msgs,
+            "sequence": self.sequence.to_string(),
+        });
+
+        if self.timeout_height.value() != 0 {
+            doc["timeout_height"] = json!(self.timeout_height.value().to_string());
+        }
+
+        doc
+    }
+
+    /// Compute the sign bytes for this [`StdSignDoc`].
+    ///
+    /// These are the Amino JSON serialization of this document with all object
+    /// keys sorted, escaped using the same rules as Golang's `encoding/json`.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let json = serde_json::to_string(&sort_json(self.to_json()))?;
+        let mut escaped = String::with_capacity(json.len());
+
+        for c in json.chars() {
+            match c {
+                '<' => escaped.push_str("\\u003c"),
+                '>' => escaped.push_str("\\u003e"),
+                '&' => escaped.push_str("\\u0026"),
+                '\u{2028}' => escaped.push_str("\\u2028"),
+                '\u{2029}' => escaped.push_str("\\u2029"),
+                _ => escaped.push(c),
+            }
+        }
+
+        Ok(escaped.into_bytes())
+    }
+
+    /// Sign this [`StdSignDoc`], producing a signature.
+    pub fn sign<S: TxSigner + ?Sized>(&self, signing_key: &S) -> Result<SignatureBytes> {
+        signing_key.sign_tx(&self.to_bytes()?)
+    }
+
+    /// Sign this [`StdSignDoc`] with an [`AsyncTxSigner`], producing a signature.
+    pub async fn sign_async<S: AsyncTxSigner + ?Sized>(
+        &self,
+        signer: &S,
+    ) -> Result<SignatureBytes> {
+        signer.sign_tx_async(&self.to_bytes()?).await
+    }
+}
+
+/// Serialize a message [`Any`] as Amino JSON.
+fn msg_to_amino_json(any: &Any) -> Result<Value> {
+    AnyMsg::from_any(any)?.to_amino_json()
+}
+
+/// Recursively sort the keys of all JSON objects contained in the given value.
+fn sort_json(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries = map.into_iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k, sort_json(v)))
+                    .collect(),
+            )
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(sort_json).collect()),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StdSignDoc;
+    use crate::{
+        bank::MsgSend,
+        tx::{Body, Fee, Msg},
+        Coin,
+    };
+
+    #[test]
+    fn msg_send_sign_bytes() {
+        let amount = Coin {
+            denom: "uatom".parse().unwrap(),
+            amount: 1_000_000u64.into(),
+        };
+
+        let msg_send = MsgSend {
+            from_address: "cosmos1qyqszqgpqyqszqgpqyqszqgpqyqszqgpjnp7du"
+                .parse()
+                .unwrap(),
+            to_address: "cosmos19dyl0uyzes4k23lscla02n06fc22h4uqsdwq6z"
+                .parse()
+                .unwrap(),
+            amount: vec![amount],
+        };
+
+        let fee = Fee::from_amount_and_gas(
+            Coin {
+                denom: "uatom".parse().unwrap(),
+                amount: 5_000u64.into(),
+            },
+            200_000u64,
+        );
+
+        let body = Body::new(vec![msg_send.to_any().unwrap()], "<memo> & more", 0u16);
+        let chain_id = "cosmoshub-4".parse().unwrap();
+        let sign_doc = StdSignDoc::new(&body, &fee, &chain_id, 1, 42).unwrap();
+
+        assert_eq!(
+            String::from_utf8(sign_doc.to_bytes().unwrap()).unwrap(),
+            concat!(
+                r#"{"account_number":"1","chain_id":"cosmoshub-4","#,
+                r#""fee":{"amount":[{"amount":"5000","denom":"uatom"}],"gas":"200000"},"#,
+                r#""memo":"\u003cmemo\u003e \u0026 more","#,
+                r#""msgs":[{"type":"cosmos-sdk/MsgSend","value":{"#,
+                r#""amount":[{"amount":"1000000","denom":"uatom"}],"#,
+                r#""from_address":"cosmos1qyqszqgpqyqszqgpqyqszqgpqyqszqgpjnp7du","#,
+                r#""to_address":"cosmos19dyl0uyzes4k23lscla02n06fc22h4uqsdwq6z"}}],"#,
+                r#""sequence":"42"}"#
+            )
+        );
+    }
+}
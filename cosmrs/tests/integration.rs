@@ -93,9 +93,12 @@ fn msg_send() {
                 panic!("deliver_tx failed: {:?}", tx_commit_response.deliver_tx);
             }
 
+            assert_eq!(tx_raw.hash().unwrap(), tx_commit_response.hash);
+
             let tx = dev::poll_for_tx(&rpc_client, tx_commit_response.hash).await;
             assert_eq!(&tx_body, &tx.body);
             assert_eq!(&auth_info, &tx.auth_info);
+            assert_eq!(tx.hash().unwrap(), tx_commit_response.hash);
         })
     });
 }